@@ -0,0 +1,190 @@
+use std::{cmp::Ordering::*, fmt, str::FromStr};
+
+use poker::{
+    card::Card,
+    game::betting::Action,
+    hand::{Hand, Variant},
+    player::Status,
+    Game,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Number of boards the bots deal out to judge their hands.
+const TRIALS: usize = 300;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Style {
+    Tight,
+    Loose,
+    Aggressive,
+    Passive,
+}
+
+pub const STYLES: [Style; 4] = [
+    Style::Tight,
+    Style::Loose,
+    Style::Aggressive,
+    Style::Passive,
+];
+
+/// How a style turns the strength of its hand into an action.
+struct Tendencies {
+    /// How much more than an even share of the pot the hand must be expected
+    /// to win before the bot bets or raises.
+    raise_edge: f64,
+    /// Chance of betting or raising with a hand that is strong enough.
+    raise_chance: f64,
+    /// How far below the pot odds the bot will still call.
+    call_margin: f64,
+    /// Chance of betting when checked to, whatever the hand.
+    bluff_chance: f64,
+}
+
+impl Style {
+    fn tendencies(self) -> Tendencies {
+        let (raise_edge, raise_chance, call_margin, bluff_chance) = match self {
+            Style::Tight => (0.15, 0.7, -0.05, 0.02),
+            Style::Loose => (0.1, 0.5, 0.1, 0.1),
+            Style::Aggressive => (0.05, 0.9, 0.05, 0.25),
+            Style::Passive => (0.25, 0.2, 0.05, 0.0),
+        };
+        Tendencies {
+            raise_edge,
+            raise_chance,
+            call_margin,
+            bluff_chance,
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Style::Tight => "tight",
+            Style::Loose => "loose",
+            Style::Aggressive => "aggressive",
+            Style::Passive => "passive",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        STYLES
+            .into_iter()
+            .find(|style| style.to_string() == s)
+            .ok_or_else(|| format!("Unknown style '{s}'"))
+    }
+}
+
+/// A computer opponent. It only looks at what its seat can see.
+pub struct Bot {
+    pub style: Style,
+    variant: Variant,
+    rng: StdRng,
+}
+
+impl Bot {
+    pub fn new(style: Style, variant: Variant, rng: StdRng) -> Self {
+        Self {
+            style,
+            variant,
+            rng,
+        }
+    }
+
+    /// Chooses an action for the bot in this seat, which must be the seat in turn.
+    pub fn decide(&mut self, game: &Game, seat: usize) -> Action {
+        let view = game.view_for(seat);
+        let cards = view
+            .seats
+            .get(seat)
+            .and_then(|player| player.as_ref()?.cards.clone())
+            .unwrap_or_default();
+        let opponents = view
+            .seats
+            .iter()
+            .enumerate()
+            .filter(|&(position, player)| {
+                position != seat
+                    && player
+                        .as_ref()
+                        .is_some_and(|p| matches!(p.status, Status::Active | Status::AllIn))
+            })
+            .count()
+            .max(1);
+        let strength = self.win_chance(&cards, &view.board, opponents);
+        let pot = view.pot + view.seats.iter().flatten().map(|p| p.bet).sum::<u64>();
+        let to_call = game.to_call();
+        let tendencies = self.style.tendencies();
+        if let Some((min, max)) = game.raise_range() {
+            let share = 1.0 / (opponents + 1) as f64;
+            let strong = strength > share + tendencies.raise_edge
+                && self.rng.gen_bool(tendencies.raise_chance);
+            let bluff = to_call == 0 && self.rng.gen_bool(tendencies.bluff_chance);
+            if strong || bluff {
+                // Bets and raises are the size of the pot.
+                let amount = (view.current_bet + pot + to_call).clamp(min, max);
+                return match view.current_bet {
+                    0 => Action::Bet(amount),
+                    _ => Action::Raise(amount),
+                };
+            }
+        }
+        if to_call == 0 {
+            return Action::Check;
+        }
+        let pot_odds = to_call as f64 / (pot + to_call) as f64;
+        match strength + tendencies.call_margin >= pot_odds {
+            true => Action::Call,
+            false => Action::Fold,
+        }
+    }
+
+    /// Share of the pot the hand wins against random hands, dealing out the rest of the board.
+    fn win_chance(&mut self, cards: &[Card], board: &[Card], opponents: usize) -> f64 {
+        if cards.len() != 2 {
+            return 0.0;
+        }
+        let variant = self.variant;
+        let mut deck: Vec<Card> = variant
+            .deck()
+            .into_iter()
+            .filter(|card| !cards.contains(card) && !board.contains(card))
+            .collect();
+        let needed = 2 * opponents + 5 - board.len();
+        if deck.len() < needed {
+            return 0.0;
+        }
+        let mut won = 0.0;
+        for _ in 0..TRIALS {
+            let (drawn, _) = deck.partial_shuffle(&mut self.rng, needed);
+            let (others, rest) = drawn.split_at(2 * opponents);
+            let full_board = [board, rest].concat();
+            let hand_of = |hole: &[Card]| -> Hand {
+                let cards: [Card; 7] = [hole, &full_board].concat().try_into().unwrap();
+                variant.best_hand(cards)
+            };
+            let hand = hand_of(cards);
+            let mut tied = 1;
+            let mut lost = false;
+            for hole in others.chunks(2) {
+                match variant.cmp_hands(&hand_of(hole), &hand) {
+                    Greater => {
+                        lost = true;
+                        break;
+                    }
+                    Equal => tied += 1,
+                    Less => (),
+                }
+            }
+            if !lost {
+                won += 1.0 / tied as f64;
+            }
+        }
+        won / TRIALS as f64
+    }
+}
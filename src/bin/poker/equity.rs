@@ -0,0 +1,97 @@
+use poker::{
+    card::{parse_cards, Card},
+    equity::Equity,
+};
+
+use crate::{json_cards, parse_value, symbols};
+
+/// Prints how often each hand wins against the others.
+pub fn run(args: &[String]) -> Result<(), String> {
+    println!("{}", report(args)?);
+    Ok(())
+}
+
+/// How often each hand wins against the others, as a table or as JSON.
+pub fn report(args: &[String]) -> Result<String, String> {
+    let mut json = false;
+    let mut iterations: usize = 100_000;
+    let mut board = Vec::new();
+    let mut dead = Vec::new();
+    let mut hands: Vec<[Card; 2]> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--board" => board = parse_cards(&parse_value::<String>(arg, args.next())?)?,
+            "--dead" => dead = parse_cards(&parse_value::<String>(arg, args.next())?)?,
+            "--iterations" => {
+                iterations = parse_value(arg, args.next())?;
+                if iterations == 0 {
+                    return Err(String::from("--iterations must be at least 1"));
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'")),
+            hand => {
+                let cards = parse_cards(hand)?;
+                let hand = cards
+                    .try_into()
+                    .map_err(|_| format!("A hand must have two cards, not '{hand}'"))?;
+                hands.push(hand);
+            }
+        }
+    }
+    #[cfg(feature = "parallel")]
+    let results = poker::equity::par_equity(&hands, &board, &dead, iterations)?;
+    #[cfg(not(feature = "parallel"))]
+    let results = poker::equity::equity(&hands, &board, &dead, iterations)?;
+    if json {
+        return Ok(to_json(&hands, &board, &dead, iterations, &results));
+    }
+    let mut lines = Vec::new();
+    if !board.is_empty() {
+        lines.push(format!("Board: {}", symbols(&board)));
+    }
+    if !dead.is_empty() {
+        lines.push(format!("Dead: {}", symbols(&dead)));
+    }
+    lines.push(format!("{iterations} boards"));
+    lines.push(String::from("Hand        Win      Tie   Equity"));
+    for (hand, result) in hands.iter().zip(&results) {
+        lines.push(format!(
+            "{:<7} {:>7.2}% {:>7.2}% {:>7.2}%",
+            symbols(hand),
+            result.win_rate() * 100.0,
+            result.tie_rate() * 100.0,
+            result.equity() * 100.0
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn to_json(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    iterations: usize,
+    results: &[Equity],
+) -> String {
+    let hands: Vec<String> = hands
+        .iter()
+        .zip(results)
+        .map(|(hand, result)| {
+            format!(
+                r#"{{"cards":{},"win":{},"tie":{},"equity":{}}}"#,
+                json_cards(hand),
+                result.win_rate(),
+                result.tie_rate(),
+                result.equity()
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"board":{},"dead":{},"iterations":{iterations},"hands":[{}]}}"#,
+        json_cards(board),
+        json_cards(dead),
+        hands.join(",")
+    )
+}
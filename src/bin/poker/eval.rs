@@ -0,0 +1,70 @@
+use poker::{
+    card::{format_card, parse_cards, Card},
+    hand::{five_card_hand, Hand},
+};
+
+use crate::{json_cards, symbols};
+
+/// Prints the best five card hand that can be made from the cards.
+pub fn run(args: &[String]) -> Result<(), String> {
+    println!("{}", report(args)?);
+    Ok(())
+}
+
+/// The best five card hand that can be made from the cards, as text or as JSON.
+pub fn report(args: &[String]) -> Result<String, String> {
+    let mut json = false;
+    let mut cards: Vec<Card> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'")),
+            arg => cards.extend(parse_cards(arg)?),
+        }
+    }
+    if !(5..=7).contains(&cards.len()) {
+        return Err(format!(
+            "Need between 5 and 7 cards to make a hand, got {}",
+            cards.len()
+        ));
+    }
+    if let Some(card) = cards
+        .iter()
+        .enumerate()
+        .find_map(|(i, card)| cards[..i].contains(card).then_some(card))
+    {
+        return Err(format!("{} is used more than once", format_card(*card)));
+    }
+    let (hand, best) = best_five(&cards);
+    let report = match json {
+        true => format!(
+            r#"{{"cards":{},"best":{},"hand":"{hand}"}}"#,
+            json_cards(&cards),
+            json_cards(&best)
+        ),
+        false => format!("{}: {hand}", symbols(&best)),
+    };
+    Ok(report)
+}
+
+/// The best hand among every five cards, and the cards that make it.
+fn best_five(cards: &[Card]) -> (Hand, [Card; 5]) {
+    let n = cards.len();
+    let mut best: Option<(Hand, [Card; 5])> = None;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        let hand = five_card_hand::best_hand(five);
+                        if best.as_ref().is_none_or(|(best, _)| hand > *best) {
+                            best = Some((hand, five));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best.expect("There are at least five cards")
+}
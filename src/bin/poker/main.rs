@@ -0,0 +1,90 @@
+//! Command line tools for playing and analysing hold'em.
+
+mod bot;
+mod equity;
+mod eval;
+mod play;
+#[cfg(test)]
+mod test;
+
+use poker::card::{format_card, Card, Suit};
+use std::{env, process::ExitCode};
+
+const USAGE: &str = "\
+Usage:
+  poker [play] [OPTIONS]    Play no limit hold'em against computer opponents
+  poker eval CARDS... [--json]
+                            Find the best hand that can be made from 5 to 7 cards
+  poker equity HAND HAND... [--board CARDS] [--dead CARDS] [--iterations N] [--json]
+                            Estimate how often each hand wins, dealing out the
+                            rest of the board at random N times (default 100000)
+
+Cards are written like AhKd, with or without spaces between them.
+
+Options for play:
+  --bots N                  Number of opponents, from 1 to 9 (default 3)
+  --style STYLE[,STYLE...]  How the bots play: tight, loose, aggressive or passive.
+                            Styles are given to the bots in turn (default all four)
+  --chips N                 Chips each player buys in for (default 200)
+  --blinds SB/BB            Small and big blinds (default 1/2)
+  --name NAME               Your name at the table (default Hero)
+  --seed N                  Seed for the shuffle and the bots, to replay the same game";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        None => play::run(&[]),
+        Some("play") => play::run(&args[1..]),
+        Some("eval") => eval::run(&args[1..]),
+        Some("equity") => equity::run(&args[1..]),
+        Some("help" | "-h" | "--help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(arg) if arg.starts_with("--") => play::run(&args),
+        Some(command) => Err(format!(
+            "Unknown command '{command}'. Run `poker help` for usage"
+        )),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads the value given for a flag.
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {flag}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{value}' for {flag}"))
+}
+
+/// Cards with suit symbols, like `A♥ K♠`.
+fn symbols(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|&(rank, suit)| {
+            let symbol = match suit {
+                Suit::Clubs => '♣',
+                Suit::Diamonds => '♦',
+                Suit::Hearts => '♥',
+                Suit::Spades => '♠',
+            };
+            format!("{}{symbol}", rank.to_char())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cards as a JSON array of short forms, like `["Ah","Kd"]`.
+fn json_cards(cards: &[Card]) -> String {
+    let cards: Vec<String> = cards
+        .iter()
+        .map(|&card| format!(r#""{}""#, format_card(card)))
+        .collect();
+    format!("[{}]", cards.join(","))
+}
@@ -0,0 +1,369 @@
+use std::io::{self, BufRead, Write};
+
+use poker::{
+    game::{
+        betting::{Action, BettingStructure},
+        event::GameEvent,
+        history::PostKind,
+        view::GameView,
+        Rake, Straddle, Street,
+    },
+    hand::Variant,
+    player::Status,
+    Game, GameOptions, Player,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    bot::{Bot, Style, STYLES},
+    parse_value, symbols,
+};
+
+/// The human player always sits in the first seat.
+const HERO: usize = 0;
+
+pub struct PlayOptions {
+    pub bots: usize,
+    pub styles: Vec<Style>,
+    pub chips: u64,
+    pub blinds: (u64, u64),
+    pub name: String,
+    pub seed: Option<u64>,
+}
+
+pub fn parse_options(args: &[String]) -> Result<PlayOptions, String> {
+    let mut options = PlayOptions {
+        bots: 3,
+        styles: STYLES.to_vec(),
+        chips: 200,
+        blinds: (1, 2),
+        name: String::from("Hero"),
+        seed: None,
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args.next();
+        match flag.as_str() {
+            "--bots" => options.bots = parse_value(flag, value)?,
+            "--style" => {
+                options.styles = parse_value::<String>(flag, value)?
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?
+            }
+            "--chips" => options.chips = parse_value(flag, value)?,
+            "--blinds" => {
+                let blinds: String = parse_value(flag, value)?;
+                let error = || format!("Invalid blinds '{blinds}', expected SB/BB");
+                let (small, big) = blinds.split_once('/').ok_or_else(error)?;
+                let small = small.parse().map_err(|_| error())?;
+                let big = big.parse().map_err(|_| error())?;
+                options.blinds = (small, big);
+            }
+            "--name" => options.name = parse_value(flag, value)?,
+            "--seed" => options.seed = Some(parse_value(flag, value)?),
+            _ => return Err(format!("Unknown option '{flag}'")),
+        }
+    }
+    if !(1..=9).contains(&options.bots) {
+        return Err(String::from("There must be between 1 and 9 bots"));
+    }
+    if options.chips < options.blinds.1 {
+        return Err(String::from("Players need at least a big blind of chips"));
+    }
+    Ok(options)
+}
+
+/// Plays hands against the bots until the player quits.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_options(args)?;
+    let (mut game, mut bots) = new_game(&options)?;
+    for seat in 1..=options.bots {
+        let style = options.styles[(seat - 1) % options.styles.len()];
+        println!("Bot {seat} sits down and plays {style}");
+    }
+    game.set_events_enabled(true);
+
+    let mut input = io::stdin().lock().lines().map_while(Result::ok);
+    loop {
+        game.start_hand()?;
+        narrate(&mut game);
+        while game.street().is_some() {
+            let seat = game.turn();
+            let action = match seat {
+                HERO => {
+                    render(&game.view_for(HERO));
+                    match prompt(&game, &mut input) {
+                        Some(action) => action,
+                        None => return Ok(()),
+                    }
+                }
+                _ => bots[seat - 1].decide(&game, seat),
+            };
+            match game.act(action) {
+                Ok(()) => narrate(&mut game),
+                Err(error) if seat == HERO => println!("{error}"),
+                Err(error) => return Err(format!("Bot {seat} made an illegal move: {error}")),
+            }
+        }
+        for seat in 1..=options.bots {
+            if game.player(seat).is_some_and(|p| p.chips == 0) {
+                game.add_chips(seat, options.chips)?;
+                println!("Bot {seat} rebuys for {}", options.chips);
+            }
+        }
+        if game.player(HERO).is_some_and(|p| p.chips == 0) {
+            if !ask(&format!("Rebuy for {}? [Y/n]", options.chips), &mut input) {
+                return Ok(());
+            }
+            game.add_chips(HERO, options.chips)?;
+        } else if !ask("Press Enter for the next hand, or q to quit", &mut input) {
+            return Ok(());
+        }
+    }
+}
+
+/// Seats the player and the bots at a new table.
+pub fn new_game(options: &PlayOptions) -> Result<(Game, Vec<Bot>), String> {
+    let (small_blind, big_blind) = options.blinds;
+    let mut game = Game::new(GameOptions {
+        num_seats: options.bots + 1,
+        min_buyin: big_blind,
+        max_buyin: options.chips,
+        small_blind,
+        big_blind,
+        variant: Variant::Standard,
+        betting: BettingStructure::NoLimit,
+        ante: 0,
+        big_blind_ante: false,
+        straddle: Straddle::Off,
+        rake: Rake::default(),
+    })?;
+    if let Some(seed) = options.seed {
+        game.set_seed(seed);
+    }
+    game.seat_player(HERO, Player::new(0, &options.name, options.chips))?;
+    let mut bots = Vec::new();
+    for seat in 1..=options.bots {
+        let style = options.styles[(seat - 1) % options.styles.len()];
+        let rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(seat as u64)),
+            None => StdRng::from_entropy(),
+        };
+        bots.push(Bot::new(style, Variant::Standard, rng));
+        let name = format!("Bot {seat}");
+        game.seat_player(seat, Player::new(seat as u64, &name, options.chips))?;
+    }
+    Ok((game, bots))
+}
+
+/// Asks a question, returning `false` if the answer is no or there is no more input.
+fn ask(question: &str, input: &mut impl Iterator<Item = String>) -> bool {
+    print!("{question} ");
+    io::stdout().flush().ok();
+    match input.next() {
+        Some(answer) => !matches!(answer.trim(), "n" | "no" | "q" | "quit"),
+        None => false,
+    }
+}
+
+/// Asks the player for an action until they give a legal one, or `None` if they quit.
+fn prompt(game: &Game, input: &mut impl Iterator<Item = String>) -> Option<Action> {
+    let to_call = game.to_call();
+    let bet = match game.current_bet() {
+        0 => "bet",
+        _ => "raise to",
+    };
+    let mut choices = Vec::new();
+    let default = match to_call {
+        0 => {
+            choices.push(String::from("[c]heck"));
+            Action::Check
+        }
+        _ => {
+            choices.push(String::from("[f]old"));
+            choices.push(format!("[c]all {to_call}"));
+            Action::Call
+        }
+    };
+    match game.raise_range() {
+        Some((min, max)) if min == max => choices.push(format!("[r] {bet} {min}")),
+        Some((min, max)) => choices.push(format!("[r] {bet} {min}-{max}")),
+        None => (),
+    }
+    choices.push(String::from("[a]ll in"));
+    choices.push(String::from("[q]uit"));
+    loop {
+        print!(
+            "{} (Enter to {}) > ",
+            choices.join(", "),
+            describe(default, to_call)
+        );
+        io::stdout().flush().ok();
+        let line = input.next()?;
+        match parse_action(line.trim(), game, default) {
+            Ok(Some(action)) => return Some(action),
+            Ok(None) => return None,
+            Err(error) => println!("{error}"),
+        }
+    }
+}
+
+/// Reads an action typed by the player, or `None` if they want to quit.
+pub fn parse_action(line: &str, game: &Game, default: Action) -> Result<Option<Action>, String> {
+    let (command, amount) = match line.split_once(' ') {
+        Some((command, amount)) => (command, Some(amount.trim())),
+        None if line.starts_with(|c: char| c.is_ascii_digit()) => ("r", Some(line)),
+        None => match line.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => (&line[..i], Some(&line[i..])),
+            None => (line, None),
+        },
+    };
+    let action = match command {
+        "" => default,
+        "f" | "fold" => Action::Fold,
+        "c" | "k" | "check" | "call" => match game.to_call() {
+            0 => Action::Check,
+            _ => Action::Call,
+        },
+        "a" | "all" | "allin" => Action::AllIn,
+        "q" | "quit" => return Ok(None),
+        "r" | "b" | "raise" | "bet" => {
+            let (min, _) = game.raise_range().ok_or("You cannot raise")?;
+            let amount = match amount {
+                Some(amount) => amount
+                    .parse()
+                    .map_err(|_| format!("Invalid amount '{amount}'"))?,
+                None => min,
+            };
+            match game.current_bet() {
+                0 => Action::Bet(amount),
+                _ => Action::Raise(amount),
+            }
+        }
+        _ => return Err(format!("Unknown action '{line}'")),
+    };
+    Ok(Some(action))
+}
+
+fn describe(action: Action, to_call: u64) -> String {
+    match action {
+        Action::Fold => String::from("fold"),
+        Action::Check => String::from("check"),
+        Action::Call => format!("call {to_call}"),
+        Action::Bet(amount) => format!("bet {amount}"),
+        Action::Raise(amount) => format!("raise to {amount}"),
+        Action::AllIn => String::from("go all in"),
+    }
+}
+
+fn street_name(street: Street) -> &'static str {
+    match street {
+        Street::Preflop => "Preflop",
+        Street::Flop => "Flop",
+        Street::Turn => "Turn",
+        Street::River => "River",
+    }
+}
+
+/// Draws the table as the player sees it.
+fn render(view: &GameView) {
+    let bets: u64 = view.seats.iter().flatten().map(|p| p.bet).sum();
+    println!();
+    println!(
+        "{}  Pot: {}",
+        view.street.map_or("", street_name),
+        view.pot + bets
+    );
+    match view.board.is_empty() {
+        true => println!("Board: -"),
+        false => println!("Board: {}", symbols(&view.board)),
+    }
+    for (position, player) in view.seats.iter().enumerate() {
+        let Some(player) = player else { continue };
+        let turn = if view.turn == Some(position) {
+            ">"
+        } else {
+            " "
+        };
+        let button = if view.button == position { "(D)" } else { "" };
+        let bet = match player.bet {
+            0 => String::new(),
+            bet => format!("bet {bet}"),
+        };
+        let cards = match (&player.cards, player.status) {
+            (None, _) => String::from("?? ??"),
+            (_, Status::Folded) => String::from("folded"),
+            (_, Status::SittingOut) => String::from("sitting out"),
+            (Some(cards), _) => symbols(cards),
+        };
+        println!(
+            "{turn} {:<12} {:>6}  {bet:<8} {cards:<12} {button}",
+            player.name, player.chips
+        );
+    }
+}
+
+/// Prints what happened at the table since it was last narrated.
+fn narrate(game: &mut Game) {
+    let events = game.take_events();
+    let name = |position: usize| {
+        game.player(position)
+            .map_or_else(|| format!("Seat {position}"), |p| p.name.clone())
+    };
+    for event in events {
+        match event.visible_to(Some(HERO)) {
+            GameEvent::HandStarted { number, button } => {
+                println!("\n*** Hand #{number} *** {} has the button", name(button))
+            }
+            GameEvent::CardsDealt {
+                position,
+                cards: Some(cards),
+            } => println!("Dealt to {}: {}", name(position), symbols(&cards)),
+            GameEvent::Posted(post) => {
+                let kind = match post.kind {
+                    PostKind::Ante => "an ante",
+                    PostKind::SmallBlind => "the small blind",
+                    PostKind::BigBlind => "the big blind",
+                    PostKind::Straddle => "a straddle",
+                    PostKind::Dead => "a dead small blind",
+                };
+                println!("{} posts {kind} of {}", name(post.position), post.chips)
+            }
+            GameEvent::ActionTaken(record) => {
+                let action = match record.action {
+                    Action::Fold => String::from("folds"),
+                    Action::Check => String::from("checks"),
+                    Action::Call => format!("calls {}", record.chips),
+                    Action::Bet(amount) => format!("bets {amount}"),
+                    Action::Raise(amount) => format!("raises to {amount}"),
+                    Action::AllIn => String::from("goes all in"),
+                };
+                let all_in = if record.all_in { " and is all in" } else { "" };
+                println!("{} {action}{all_in}", name(record.position))
+            }
+            GameEvent::BetReturned { position, chips } => {
+                println!("Uncalled bet of {chips} returned to {}", name(position))
+            }
+            GameEvent::StreetAdvanced { street, cards } => println!(
+                "*** {} *** {}",
+                street_name(street).to_uppercase(),
+                symbols(&cards)
+            ),
+            GameEvent::Showdown {
+                position,
+                cards,
+                hand,
+            } => println!("{} shows {} ({hand})", name(position), symbols(&cards)),
+            GameEvent::PotAwarded(award) => {
+                let pot = match award.pot {
+                    0 => String::from("the pot"),
+                    pot => format!("side pot {pot}"),
+                };
+                println!("{} wins {} from {pot}", name(award.position), award.chips)
+            }
+            GameEvent::Busted { position, .. } => println!("{} is out of chips", name(position)),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use poker::{game::betting::Action::*, hand::Variant};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    bot::{Bot, Style},
+    equity, eval,
+    play::{new_game, parse_action, parse_options},
+};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|&arg| String::from(arg)).collect()
+}
+
+#[test]
+fn play_options() {
+    let options = parse_options(&[]).unwrap();
+    assert_eq!(options.bots, 3);
+    assert_eq!(options.blinds, (1, 2));
+    let options = parse_options(&args(&[
+        "--bots",
+        "2",
+        "--style",
+        "tight,loose",
+        "--blinds",
+        "5/10",
+        "--seed",
+        "7",
+    ]))
+    .unwrap();
+    assert_eq!(options.bots, 2);
+    assert_eq!(options.styles, [Style::Tight, Style::Loose]);
+    assert_eq!(options.blinds, (5, 10));
+    assert_eq!(options.seed, Some(7));
+
+    let error = |a: &[&str]| parse_options(&args(a)).err().unwrap();
+    assert_eq!(
+        error(&["--bots", "0"]),
+        "There must be between 1 and 9 bots"
+    );
+    assert_eq!(error(&["--style", "wild"]), "Unknown style 'wild'");
+    assert_eq!(
+        error(&["--blinds", "1-2"]),
+        "Invalid blinds '1-2', expected SB/BB"
+    );
+    assert_eq!(error(&["--chips"]), "Missing value for --chips");
+    assert_eq!(error(&["--deal"]), "Unknown option '--deal'");
+}
+
+#[test]
+fn actions() {
+    let options = parse_options(&args(&["--bots", "2", "--seed", "1"])).unwrap();
+    let (mut game, _) = new_game(&options).unwrap();
+    game.start_hand().unwrap();
+    // The player is on the button facing the big blind of 2.
+    assert_eq!(game.turn(), 0);
+    let parse = |line| parse_action(line, &game, Call);
+    assert_eq!(parse(""), Ok(Some(Call)));
+    assert_eq!(parse("f"), Ok(Some(Fold)));
+    assert_eq!(parse("check"), Ok(Some(Call)));
+    assert_eq!(parse("a"), Ok(Some(AllIn)));
+    assert_eq!(parse("r"), Ok(Some(Raise(4))));
+    assert_eq!(parse("r 10"), Ok(Some(Raise(10))));
+    assert_eq!(parse("r10"), Ok(Some(Raise(10))));
+    assert_eq!(parse("10"), Ok(Some(Raise(10))));
+    assert_eq!(parse("q"), Ok(None));
+    assert_eq!(parse("r ten"), Err(String::from("Invalid amount 'ten'")));
+    assert_eq!(parse("x"), Err(String::from("Unknown action 'x'")));
+}
+
+#[test]
+fn bots_only() {
+    let options = parse_options(&args(&["--bots", "5", "--seed", "42"])).unwrap();
+    let (mut game, mut bots) = new_game(&options).unwrap();
+    // A bot plays the player's seat too.
+    bots.insert(
+        0,
+        Bot::new(Style::Tight, Variant::Standard, StdRng::seed_from_u64(0)),
+    );
+    let mut chips = 6 * options.chips;
+    for _ in 0..30 {
+        game.start_hand().unwrap();
+        while game.street().is_some() {
+            let seat = game.turn();
+            let action = bots[seat].decide(&game, seat);
+            assert_eq!(game.act(action), Ok(()), "Seat {seat} {action:?}");
+            assert_eq!(game.check_chips(), Ok(()));
+        }
+        for seat in 0..bots.len() {
+            if game.player(seat).is_some_and(|p| p.chips == 0) {
+                game.add_chips(seat, options.chips).unwrap();
+                chips += options.chips;
+            }
+        }
+        let total: u64 = (0..bots.len())
+            .filter_map(|seat| game.player(seat))
+            .map(|player| player.chips)
+            .sum();
+        assert_eq!(total, chips);
+    }
+}
+
+#[test]
+fn eval() {
+    let report = eval::report(&args(&["AhKhQhJhTh", "2c", "3d"])).unwrap();
+    assert_eq!(report, "A♥ K♥ Q♥ J♥ T♥: a Royal Flush");
+    let report = eval::report(&args(&["2c3c4c5c", "7d", "--json"])).unwrap();
+    assert_eq!(
+        report,
+        r#"{"cards":["2c","3c","4c","5c","7d"],"best":["2c","3c","4c","5c","7d"],"hand":"high card Seven"}"#
+    );
+
+    let error = |a: &[&str]| eval::report(&args(a)).unwrap_err();
+    assert_eq!(
+        error(&["AhKh"]),
+        "Need between 5 and 7 cards to make a hand, got 2"
+    );
+    assert_eq!(error(&["AhAhQhJhTh"]), "Ah is used more than once");
+    assert_eq!(
+        error(&["AhKhQhJhTh", "--pretty"]),
+        "Unknown option '--pretty'"
+    );
+}
+
+#[test]
+fn equity() {
+    // With the whole board dealt, every trial ends the same way.
+    let board = [
+        "AhKh",
+        "QsQd",
+        "--board",
+        "2c3c4c5d9s",
+        "--iterations",
+        "10",
+    ];
+    let report = equity::report(&args(&board)).unwrap();
+    assert_eq!(
+        report.lines().collect::<Vec<_>>(),
+        [
+            "Board: 2♣ 3♣ 4♣ 5♦ 9♠",
+            "10 boards",
+            "Hand        Win      Tie   Equity",
+            "A♥ K♥    100.00%    0.00%  100.00%",
+            "Q♠ Q♦      0.00%    0.00%    0.00%",
+        ]
+    );
+    let report = equity::report(&args(&[&board[..], &["--json"]].concat())).unwrap();
+    assert_eq!(
+        report,
+        r#"{"board":["2c","3c","4c","5d","9s"],"dead":[],"iterations":10,"hands":[{"cards":["Ah","Kh"],"win":1,"tie":0,"equity":1},{"cards":["Qs","Qd"],"win":0,"tie":0,"equity":0}]}"#
+    );
+
+    let error = |a: &[&str]| equity::report(&args(a)).unwrap_err();
+    assert_eq!(
+        error(&["AhKh", "QsQd", "--iterations", "0"]),
+        "--iterations must be at least 1"
+    );
+    assert_eq!(
+        error(&["AhKh", "QsQd", "--iterations", "many"]),
+        "Invalid value 'many' for --iterations"
+    );
+    assert_eq!(error(&["AhKh", "--board"]), "Missing value for --board");
+    assert_eq!(
+        error(&["AhKhQs", "QsQd"]),
+        "A hand must have two cards, not 'AhKhQs'"
+    );
+}
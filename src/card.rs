@@ -1,5 +1,8 @@
 use self::{Rank::*, Suit::*};
 
+#[cfg(feature = "serde")]
+pub mod as_string;
+
 #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
 pub enum Rank {
     Two,
@@ -17,7 +20,67 @@ pub enum Rank {
     Ace,
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+impl Rank {
+    pub fn to_char(self) -> char {
+        match self {
+            Two => '2',
+            Three => '3',
+            Four => '4',
+            Five => '5',
+            Six => '6',
+            Seven => '7',
+            Eight => '8',
+            Nine => '9',
+            Ten => 'T',
+            Jack => 'J',
+            Queen => 'Q',
+            King => 'K',
+            Ace => 'A',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        RANKS.into_iter().find(|rank| rank.to_char() == c)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Two => "Two",
+            Three => "Three",
+            Four => "Four",
+            Five => "Five",
+            Six => "Six",
+            Seven => "Seven",
+            Eight => "Eight",
+            Nine => "Nine",
+            Ten => "Ten",
+            Jack => "Jack",
+            Queen => "Queen",
+            King => "King",
+            Ace => "Ace",
+        }
+    }
+
+    pub fn plural(self) -> &'static str {
+        match self {
+            Two => "Twos",
+            Three => "Threes",
+            Four => "Fours",
+            Five => "Fives",
+            Six => "Sixes",
+            Seven => "Sevens",
+            Eight => "Eights",
+            Nine => "Nines",
+            Ten => "Tens",
+            Jack => "Jacks",
+            Queen => "Queens",
+            King => "Kings",
+            Ace => "Aces",
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
 pub enum Suit {
     Clubs,
     Diamonds,
@@ -25,8 +88,102 @@ pub enum Suit {
     Spades,
 }
 
+impl Suit {
+    pub fn to_char(self) -> char {
+        match self {
+            Clubs => 'c',
+            Diamonds => 'd',
+            Hearts => 'h',
+            Spades => 's',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        SUITS.into_iter().find(|suit| suit.to_char() == c)
+    }
+}
+
 pub type Card = (Rank, Suit);
 
+/// Short form of a card, like `Ah` for the ace of hearts.
+pub fn format_card((rank, suit): Card) -> String {
+    format!("{}{}", rank.to_char(), suit.to_char())
+}
+
+/// Short forms of the cards separated by spaces, like `Ah Kd`.
+pub fn format_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|&card| format_card(card))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads a card in short form, like `Ah`.
+pub fn parse_card(s: &str) -> Result<Card, String> {
+    let error = || format!("Invalid card '{s}'");
+    let mut chars = s.chars();
+    let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+        return Err(error());
+    };
+    let rank = Rank::from_char(rank.to_ascii_uppercase()).ok_or_else(error)?;
+    let suit = Suit::from_char(suit.to_ascii_lowercase()).ok_or_else(error)?;
+    Ok((rank, suit))
+}
+
+/// Reads cards in short form, with or without spaces between them, like `AhKd` or `Ah Kd`.
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, String> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if !chars.len().is_multiple_of(2) {
+        return Err(format!("Invalid cards '{s}'"));
+    }
+    chars
+        .chunks(2)
+        .map(|card| parse_card(&card.iter().collect::<String>()))
+        .collect()
+}
+
+/// Ranks from lowest to highest.
+pub const RANKS: [Rank; 13] = [
+    Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace,
+];
+
+pub const SUITS: [Suit; 4] = [Clubs, Diamonds, Hearts, Spades];
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A card from a deck that may contain jokers.
+pub enum PlayingCard {
+    Card(#[cfg_attr(feature = "serde", serde(with = "as_string::card"))] Card),
+    Joker,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Options for building decks for wild card games.
+pub struct DeckOptions {
+    /// Number of jokers to add to the deck. Jokers are always wild.
+    pub jokers: usize,
+    /// Ranks that are wild, e.g. `vec![Two]` for deuces wild.
+    pub wild_ranks: Vec<Rank>,
+}
+
+impl DeckOptions {
+    pub fn is_wild(&self, card: PlayingCard) -> bool {
+        match card {
+            PlayingCard::Card((rank, _)) => self.wild_ranks.contains(&rank),
+            PlayingCard::Joker => true,
+        }
+    }
+}
+
+/// A 52 card deck with the jokers from the options added at the end.
+pub fn deck(options: &DeckOptions) -> Vec<PlayingCard> {
+    let mut deck: Vec<PlayingCard> = deck_52().into_iter().map(PlayingCard::Card).collect();
+    deck.extend((0..options.jokers).map(|_| PlayingCard::Joker));
+    deck
+}
+
 pub fn deck_52() -> Vec<Card> {
     vec![
         (Two, Clubs),
@@ -83,3 +240,8 @@ pub fn deck_52() -> Vec<Card> {
         (Ace, Spades),
     ]
 }
+
+/// Deck for short deck hold'em, with the twos through fives removed.
+pub fn deck_36() -> Vec<Card> {
+    deck_52().into_iter().filter(|&(r, _)| r >= Six).collect()
+}
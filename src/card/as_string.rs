@@ -0,0 +1,168 @@
+//! Serde helpers that write cards in short form, like `"Ah"`, for use with `#[serde(with)]`.
+//!
+//! `Card` is a tuple, so on its own it serializes as `["A","h"]`. Wrap it in
+//! `CardString` to serialize a bare card in short form.
+
+use super::{format_card, parse_card, Card, PlayingCard, Rank, Suit};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.to_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Rank::from_char(c).ok_or_else(|| de::Error::custom(format!("Invalid rank '{c}'")))
+    }
+}
+
+impl Serialize for Suit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.to_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Suit::from_char(c).ok_or_else(|| de::Error::custom(format!("Invalid suit '{c}'")))
+    }
+}
+
+/// Cards that can be written in short form.
+pub trait ShortForm: Sized {
+    fn short_form(&self) -> String;
+    fn from_short_form(s: &str) -> Result<Self, String>;
+}
+
+impl ShortForm for Card {
+    fn short_form(&self) -> String {
+        format_card(*self)
+    }
+
+    fn from_short_form(s: &str) -> Result<Self, String> {
+        parse_card(s)
+    }
+}
+
+/// Jokers are written `Jk`.
+impl ShortForm for PlayingCard {
+    fn short_form(&self) -> String {
+        match self {
+            PlayingCard::Card(card) => format_card(*card),
+            PlayingCard::Joker => String::from("Jk"),
+        }
+    }
+
+    fn from_short_form(s: &str) -> Result<Self, String> {
+        match s {
+            "Jk" => Ok(PlayingCard::Joker),
+            _ => parse_card(s).map(PlayingCard::Card),
+        }
+    }
+}
+
+/// A card that serializes in short form.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct CardString(pub Card);
+
+impl From<Card> for CardString {
+    fn from(card: Card) -> Self {
+        CardString(card)
+    }
+}
+
+impl Serialize for CardString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_card(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for CardString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse_card(&s).map(CardString).map_err(de::Error::custom)
+    }
+}
+
+pub mod card {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+        CardString(*card).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        Ok(CardString::deserialize(deserializer)?.0)
+    }
+}
+
+pub mod cards {
+    use super::*;
+
+    pub fn serialize<C: ShortForm, S: Serializer>(
+        cards: &[C],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cards.iter().map(ShortForm::short_form))
+    }
+
+    pub fn deserialize<'de, C: ShortForm, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<C>, D::Error> {
+        let cards = Vec::<String>::deserialize(deserializer)?;
+        cards
+            .iter()
+            .map(|card| C::from_short_form(card).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+pub mod option_cards {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        cards: &Option<Vec<Card>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let cards: Option<Vec<CardString>> = cards
+            .as_ref()
+            .map(|cards| cards.iter().map(|&card| CardString(card)).collect());
+        cards.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<Card>>, D::Error> {
+        let cards = Option::<Vec<CardString>>::deserialize(deserializer)?;
+        Ok(cards.map(|cards| cards.into_iter().map(|card| card.0).collect()))
+    }
+}
+
+/// Cards dealt to each position.
+pub mod position_cards {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        cards: &[(usize, Vec<Card>)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cards.iter().map(|(position, cards)| {
+            let cards: Vec<CardString> = cards.iter().map(|&card| CardString(card)).collect();
+            (position, cards)
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(usize, Vec<Card>)>, D::Error> {
+        let cards = Vec::<(usize, Vec<CardString>)>::deserialize(deserializer)?;
+        Ok(cards
+            .into_iter()
+            .map(|(position, cards)| (position, cards.into_iter().map(|card| card.0).collect()))
+            .collect())
+    }
+}
@@ -1,27 +1,65 @@
 use crate::card::{deck_52, Card};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 
 #[derive(Debug)]
-pub struct Dealer {
-    deck: Vec<Card>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound = "C: crate::card::as_string::ShortForm")
+)]
+/// Deals from a deck of `Card`s, or of `PlayingCard`s for games with jokers.
+pub struct Dealer<C = Card> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
+    deck: Vec<C>,
+}
+
+impl Default for Dealer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Dealer {
     pub fn new() -> Self {
-        let mut dealer = Self { deck: deck_52() };
+        Self::with_deck(deck_52())
+    }
+}
+
+impl<C> Dealer<C> {
+    pub fn with_deck(deck: Vec<C>) -> Self {
+        let mut dealer = Self { deck };
         dealer.shuffle();
         dealer
     }
 
+    /// Shuffles the deck the same way every time for the same seed, on every
+    /// version of `rand`, so recorded seeds keep replaying.
+    pub fn with_seed(deck: Vec<C>, seed: u64) -> Self {
+        let mut dealer = Self { deck };
+        dealer.shuffle_with(&mut ChaCha12Rng::seed_from_u64(seed));
+        dealer
+    }
+
+    /// Deals the cards in the order given, without shuffling.
+    pub fn stacked(mut deck: Vec<C>) -> Self {
+        deck.reverse();
+        Self { deck }
+    }
+
     pub fn shuffle(&mut self) {
-        for i in 0..self.deck.len() - 2 {
-            let j = rand::thread_rng().gen_range(i..self.deck.len());
+        self.shuffle_with(&mut rand::thread_rng())
+    }
+
+    pub fn shuffle_with(&mut self, rng: &mut impl Rng) {
+        for i in 0..self.deck.len().saturating_sub(1) {
+            let j = rng.gen_range(i..self.deck.len());
             self.deck.swap(i, j);
         }
     }
 
-    pub fn deal(&mut self, n: usize) -> Vec<Card> {
-        let mut dealt_cards: Vec<Card> = Vec::new();
+    pub fn deal(&mut self, n: usize) -> Vec<C> {
+        let mut dealt_cards: Vec<C> = Vec::new();
         for _ in 0..n {
             match self.deck.pop() {
                 Some(card) => dealt_cards.push(card),
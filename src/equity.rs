@@ -0,0 +1,180 @@
+use crate::{
+    card::{deck_52, format_card, Card},
+    hand::seven_card_hand::best_hand,
+};
+use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Ordering::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Results of an equity simulation for one hand.
+pub struct Equity {
+    /// Number of boards where this hand won outright.
+    pub wins: u64,
+    /// Number of boards where this hand split the pot.
+    pub ties: u64,
+    /// Sum of the fraction of the pot won on each board.
+    pub pot_share: f64,
+    /// Number of boards simulated. The rates are 0 when none were.
+    pub trials: u64,
+}
+
+impl Equity {
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins as f64)
+    }
+
+    pub fn tie_rate(&self) -> f64 {
+        self.rate(self.ties as f64)
+    }
+
+    /// Expected fraction of the pot won.
+    pub fn equity(&self) -> f64 {
+        self.rate(self.pot_share)
+    }
+
+    fn rate(&self, count: f64) -> f64 {
+        match self.trials {
+            0 => 0.0,
+            trials => count / trials as f64,
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn merge(&mut self, other: &Self) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.pot_share += other.pot_share;
+        self.trials += other.trials;
+    }
+}
+
+/// Monte Carlo equity of hold'em hands, completing the board at random.
+///
+/// Dead cards are removed from the deck before dealing.
+pub fn equity(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    iterations: usize,
+) -> Result<Vec<Equity>, String> {
+    let mut deck = remaining_deck(hands, board, dead, iterations)?;
+    let mut rng = rand::thread_rng();
+    let mut results = vec![Equity::default(); hands.len()];
+    for _ in 0..iterations {
+        simulate(hands, board, &mut deck, &mut rng, &mut results);
+    }
+    Ok(results)
+}
+
+/// Monte Carlo equity of hold'em hands, with the boards simulated across all cores.
+#[cfg(feature = "parallel")]
+pub fn par_equity(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    iterations: usize,
+) -> Result<Vec<Equity>, String> {
+    let deck = remaining_deck(hands, board, dead, iterations)?;
+    let results = (0..iterations)
+        .into_par_iter()
+        .fold(
+            || (deck.clone(), vec![Equity::default(); hands.len()]),
+            |(mut deck, mut results), _| {
+                simulate(
+                    hands,
+                    board,
+                    &mut deck,
+                    &mut rand::thread_rng(),
+                    &mut results,
+                );
+                (deck, results)
+            },
+        )
+        .map(|(_, results)| results)
+        .reduce(
+            || vec![Equity::default(); hands.len()],
+            |mut a, b| {
+                a.iter_mut().zip(&b).for_each(|(a, b)| a.merge(b));
+                a
+            },
+        );
+    Ok(results)
+}
+
+/// Checks the inputs to a simulation and returns the cards left to deal from.
+fn remaining_deck(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    iterations: usize,
+) -> Result<Vec<Card>, String> {
+    if hands.len() < 2 {
+        return Err(String::from("Need at least two hands"));
+    }
+    if iterations == 0 {
+        return Err(String::from("Need at least one iteration"));
+    }
+    if board.len() > 5 {
+        return Err(format!(
+            "Board can have at most 5 cards, got {}",
+            board.len()
+        ));
+    }
+    let known: Vec<Card> = hands
+        .iter()
+        .flatten()
+        .chain(board)
+        .chain(dead)
+        .copied()
+        .collect();
+    for (i, card) in known.iter().enumerate() {
+        if known[(i + 1)..].contains(card) {
+            return Err(format!("{} is used more than once", format_card(*card)));
+        }
+    }
+    let deck: Vec<Card> = deck_52()
+        .into_iter()
+        .filter(|c| !known.contains(c))
+        .collect();
+    if deck.len() < 5 - board.len() {
+        return Err(String::from("Not enough cards left to complete the board"));
+    }
+    Ok(deck)
+}
+
+fn simulate(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    deck: &mut [Card],
+    rng: &mut impl Rng,
+    results: &mut [Equity],
+) {
+    let (runout, _) = deck.partial_shuffle(rng, 5 - board.len());
+    let board = [board, runout].concat();
+    let ranked: Vec<_> = hands
+        .iter()
+        .map(|&[a, b]| best_hand([a, b, board[0], board[1], board[2], board[3], board[4]]))
+        .collect();
+    let mut winners: Vec<usize> = Vec::new();
+    for (i, hand) in ranked.iter().enumerate() {
+        match winners.first().map(|&w| hand.cmp(&ranked[w])) {
+            None | Some(Greater) => winners = vec![i],
+            Some(Equal) => winners.push(i),
+            Some(Less) => (),
+        }
+    }
+    for result in results.iter_mut() {
+        result.trials += 1;
+    }
+    let share = 1.0 / winners.len() as f64;
+    for &w in &winners {
+        match winners.len() {
+            1 => results[w].wins += 1,
+            _ => results[w].ties += 1,
+        }
+        results[w].pot_share += share;
+    }
+}
@@ -1,25 +1,156 @@
-use crate::{card::Card, dealer::Dealer, player::Player};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::{
+    cmp::Ordering::*,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-#[derive(Debug)]
+use self::{
+    betting::{Action, BettingStructure},
+    event::GameEvent,
+    history::{ActionRecord, Award, HandHistory, Post, PostKind, Seat},
+    ledger::{Ledger, Transaction},
+};
+use crate::{
+    card::Card,
+    dealer::Dealer,
+    hand::{Hand, Variant},
+    player::{Player, Status},
+};
+
+pub mod betting;
+pub mod event;
+pub mod history;
+pub mod ledger;
+pub mod pokerstars;
+pub mod replay;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod view;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameOptions {
     pub num_seats: usize,
     pub min_buyin: u64,
     pub max_buyin: u64,
     pub small_blind: u64,
     pub big_blind: u64,
+    pub variant: Variant,
+    pub betting: BettingStructure,
+    /// Ante posted by every player before the blinds.
+    pub ante: u64,
+    /// The big blind posts a single ante of `ante` for the whole table instead.
+    ///
+    /// If they cannot cover both, the big blind is posted first.
+    pub big_blind_ante: bool,
+    pub straddle: Straddle,
+    pub rake: Rake,
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Share of each pot kept by the house.
+pub struct Rake {
+    /// Share of the pot taken in hundredths of a percent, rounded down to a
+    /// whole chip.
+    pub basis_points: u64,
+    /// Most that can be taken from a pot, by the number of players dealt in.
+    ///
+    /// Each cap applies to hands with at least that many players, until the next.
+    /// With no matching cap the rake is unlimited.
+    pub caps: Vec<(usize, u64)>,
+    /// Nothing is taken from hands that end before the flop.
+    pub no_flop_no_drop: bool,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A blind of twice the big blind, posted every hand when enabled.
+///
+/// The straddle acts as the big blind: the player after it acts first preflop,
+/// the straddler acts last, and raises must be at least the size of the straddle.
+/// There is no straddle with fewer than three players.
+pub enum Straddle {
+    #[default]
+    Off,
+    /// Posted by the player to the left of the big blind.
+    UnderTheGun,
+    /// Posted by the player on the button, so preflop action starts with the small blind.
+    Button,
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     dealer: Dealer,
     seats: Vec<Option<Player>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
     board: Vec<Card>,
     button: usize,
     turn: usize,
     num_players: usize,
     pot: u64,
     options: GameOptions,
-    // TODO: sidepots
+    /// `None` between hands.
+    street: Option<Street>,
+    /// Whether each seat has acted since the last bet or raise.
+    acted: Vec<bool>,
+    /// The bet each seat last acted on this street.
+    ///
+    /// A player may only raise again once the bet has gone up by a full raise
+    /// since they acted, so all in raises that are too small do not reopen the betting.
+    acted_at: Vec<Option<u64>>,
+    /// Size of the last full bet or raise on this street.
+    last_raise: u64,
+    /// Small and big blind positions of the last hand.
+    blinds: Option<(usize, usize)>,
+    /// Player who owes a big blind and sits out the hand rather than get the button.
+    waiting: Option<usize>,
+    /// Chips each seat has put in the pot this hand, used to build side pots.
+    contributions: Vec<u64>,
+    /// Number of bets and raises on this street.
+    raises: u32,
+    /// Number of hands started.
+    hands: u64,
+    /// Number of players dealt into the current hand.
+    hand_players: usize,
+    /// Seeds the shuffle of each hand. The same generator as `StdRng`, which
+    /// can be serialized so a restored game deals the same hands.
+    rng: ChaCha12Rng,
+    /// Cards to deal the next hand from instead of shuffling.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::card::as_string::option_cards")
+    )]
+    stacked_deck: Option<Vec<Card>>,
+    /// Small and big blind positions for the next hand, when replaying a recorded hand.
+    /// There is no small blind when none was posted.
+    replayed_blinds: Option<(Option<usize>, usize)>,
+    /// Rake to take from the next hand, when replaying a hand whose rake rules are unknown.
+    replayed_rake: Option<u64>,
+    ledger: Ledger,
+    /// History of the hand in progress.
+    history: Option<HandHistory>,
+    histories: Vec<HandHistory>,
+    /// Events not yet taken. Only kept while collecting events.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    events: Vec<GameEvent>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    events_enabled: bool,
+    /// Time recorded in hand histories, in seconds since the Unix epoch.
+    /// `None` uses the system clock.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: Option<fn() -> u64>,
 }
 
 impl Game {
@@ -27,19 +158,48 @@ impl Game {
         if options.num_seats < 2 {
             return Err(String::from("Game must have at least two seats!"));
         };
+        if options.rake.basis_points > 10_000 {
+            return Err(format!(
+                "Rake must be at most 10000 basis points, not {}",
+                options.rake.basis_points
+            ));
+        }
         Ok(Self {
-            dealer: Dealer::new(),
+            dealer: Dealer::with_deck(options.variant.deck()),
             seats: vec![None; options.num_seats],
             board: Vec::new(),
             num_players: 0,
             turn: 0,
             button: 0,
             pot: 0,
+            street: None,
+            acted: vec![false; options.num_seats],
+            acted_at: vec![None; options.num_seats],
+            last_raise: 0,
+            contributions: vec![0; options.num_seats],
+            blinds: None,
+            waiting: None,
+            raises: 0,
+            hands: 0,
+            hand_players: 0,
+            rng: ChaCha12Rng::from_entropy(),
+            stacked_deck: None,
+            replayed_blinds: None,
+            replayed_rake: None,
+            ledger: Ledger::default(),
+            history: None,
+            histories: Vec::new(),
+            events: Vec::new(),
+            events_enabled: false,
+            clock: None,
             options,
         })
     }
 
     pub fn seat_player(&mut self, position: usize, player: Player) -> Result<(), String> {
+        if self.street.is_some() {
+            return Err(String::from("Cannot seat a player during a hand"));
+        }
         if player.chips < self.options.min_buyin {
             return Err(format!(
                 "Not enough chips to seat player. Min buyin is {}, player has {}",
@@ -52,6 +212,12 @@ impl Game {
                 self.options.max_buyin, player.chips
             ));
         }
+        if let Some(seat) = self.seat_of(player.id) {
+            return Err(format!(
+                "Player {} is already seated in seat {seat}",
+                player.id
+            ));
+        }
         match self.seats.get(position) {
             Some(None) => (),
             Some(Some(_)) => return Err(format!("Seat {position} is full")),
@@ -62,18 +228,30 @@ impl Game {
                 ))
             }
         };
+        self.ledger
+            .record(self.hands, player.id, Transaction::BuyIn(player.chips));
+        self.emit(GameEvent::Seated {
+            position,
+            id: player.id,
+            chips: player.chips,
+        });
         self.seats[position] = Some(player);
+        // New players post a big blind to come in, unless the big blind reaches them first.
+        if self.blinds.is_some() {
+            self.get_mut_player(position).missed_big_blind = true;
+        }
         self.num_players += 1;
         if self.num_players == 1 {
             self.button = position;
             self.turn = position
         }
+        self.debug_check_chips();
         Ok(())
     }
 
     pub fn remove_player(&mut self, position: usize) -> Result<(), String> {
-        let player = match self.seats.get(position) {
-            Some(Some(player)) => player,
+        match self.seats.get(position) {
+            Some(Some(_)) => (),
             Some(None) => return Err(format!("Seat {position} is empty")),
             None => {
                 return Err(format!(
@@ -82,97 +260,1022 @@ impl Game {
                 ))
             }
         };
+        if self.street.is_some() && self.in_hand(position) {
+            return Err(format!("Player in seat {position} is still in the hand"));
+        }
+        let Player { id, chips, .. } = *self.get_mut_player(position);
+        self.record(position, Transaction::CashOut(chips));
+        self.emit(GameEvent::Left {
+            position,
+            id,
+            chips,
+        });
         self.seats[position] = None;
         self.num_players -= 1;
+        self.debug_check_chips();
+        Ok(())
+    }
+
+    /// Adds chips to a player's stack between hands, up to the max buyin.
+    ///
+    /// Players who have gone broke rebuy, and must bring at least the min buyin.
+    pub fn add_chips(&mut self, position: usize, chips: u64) -> Result<(), String> {
+        if self.street.is_some() {
+            return Err(String::from("Cannot add chips during a hand"));
+        }
+        let (min_buyin, max_buyin) = (self.options.min_buyin, self.options.max_buyin);
+        let hand = self.hands;
+        let player = match self.seats.get_mut(position) {
+            Some(Some(player)) => player,
+            _ => return Err(format!("Seat {position} is empty")),
+        };
+        if player.chips + chips > max_buyin {
+            return Err(format!(
+                "Too many chips. Max buyin is {max_buyin}, player would have {}",
+                player.chips + chips
+            ));
+        }
+        let transaction = match player.chips {
+            0 if chips < min_buyin => {
+                return Err(format!(
+                    "Not enough chips to rebuy. Min buyin is {min_buyin}, player added {chips}"
+                ))
+            }
+            0 => Transaction::Rebuy(chips),
+            _ if chips == 0 => return Err(String::from("No chips to add")),
+            _ => Transaction::TopUp(chips),
+        };
+        player.chips += chips;
+        self.ledger.record(hand, player.id, transaction);
+        self.debug_check_chips();
+        Ok(())
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// Makes the shuffle of every following hand depend only on the seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+    }
+
+    /// Sets the clock hand histories are timed by, in seconds since the Unix
+    /// epoch. Defaults to the system clock.
+    pub fn set_clock(&mut self, clock: fn() -> u64) {
+        self.clock = Some(clock);
+    }
+
+    /// Deals the next hand from these cards instead of shuffling.
+    ///
+    /// Cards are dealt in order: two to each player starting from the first seat, then the board.
+    pub fn stack_deck(&mut self, deck: Vec<Card>) -> Result<(), String> {
+        if self.street.is_some() {
+            return Err(String::from("Cannot stack the deck during a hand"));
+        }
+        self.stacked_deck = Some(deck);
         Ok(())
     }
 
+    /// Histories of every completed hand, oldest first.
+    pub fn histories(&self) -> &[HandHistory] {
+        &self.histories
+    }
+
+    /// Starts or stops collecting events. Events are not collected by default,
+    /// so the queue does not grow when no one takes them.
+    pub fn set_events_enabled(&mut self, enabled: bool) {
+        self.events_enabled = enabled;
+        if !enabled {
+            self.events.clear();
+        }
+    }
+
+    /// Takes the events that happened since they were last taken, oldest first.
+    ///
+    /// The events include every player's hole cards. Use `GameEvent::visible_to`
+    /// before passing them on.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        if self.events_enabled {
+            self.events.push(event);
+        }
+    }
+
+    /// Checks that no chips have been created or destroyed: the chips on the
+    /// table must match the ledger, in total and for each player.
+    pub fn check_chips(&self) -> Result<(), String> {
+        let on_table = self
+            .seats
+            .iter()
+            .flatten()
+            .map(|p| p.chips + p.bet)
+            .sum::<u64>()
+            + self.pot;
+        let expected = self.ledger.bought_in() - self.ledger.cashed_out() - self.ledger.rake();
+        if on_table != expected {
+            return Err(format!(
+                "There are {on_table} chips on the table, expected {expected}"
+            ));
+        }
+        for player in self.seats.iter().flatten() {
+            let expected = self.ledger.balance(player.id);
+            if player.chips as i64 != expected {
+                return Err(format!(
+                    "Player {} has {} chips, expected {expected}",
+                    player.id, player.chips
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `check_chips` in debug builds, panicking if it fails.
+    fn debug_check_chips(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(error) = self.check_chips() {
+                panic!("{error}");
+            }
+        }
+    }
+
+    fn record(&mut self, position: usize, transaction: Transaction) {
+        let player = self.get_mut_player(position).id;
+        self.ledger.record(self.hands, player, transaction);
+    }
+
+    /// Records chips posted before the cards are dealt.
+    ///
+    /// Posts of no chips are left out, except the big blind's, so the history
+    /// shows who had the big blind even when the ante put them all in.
+    fn record_post(&mut self, position: usize, kind: PostKind, chips: u64) {
+        if chips == 0 && kind != PostKind::BigBlind {
+            return;
+        }
+        self.record(
+            position,
+            match kind {
+                PostKind::Ante => Transaction::Ante(chips),
+                _ => Transaction::Blind(chips),
+            },
+        );
+        let post = Post {
+            position,
+            kind,
+            chips,
+        };
+        self.emit(GameEvent::Posted(post));
+        if let Some(history) = self.history.as_mut() {
+            history.posts.push(post);
+        }
+    }
+
+    pub fn player(&self, position: usize) -> Option<&Player> {
+        self.seats.get(position)?.as_ref()
+    }
+
+    /// Position of the seated player with this id.
+    pub fn seat_of(&self, id: u64) -> Option<usize> {
+        self.seats
+            .iter()
+            .position(|seat| seat.as_ref().is_some_and(|player| player.id == id))
+    }
+
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// Position of the player whose turn it is.
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// Current street, or `None` if no hand is in progress.
+    pub fn street(&self) -> Option<Street> {
+        self.street
+    }
+
+    /// Chips in the pot, not including bets on the current street.
+    pub fn pot(&self) -> u64 {
+        self.pot
+    }
+
+    /// Highest bet on the current street.
+    pub fn current_bet(&self) -> u64 {
+        self.seats
+            .iter()
+            .flatten()
+            .map(|p| p.bet)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Chips the player in turn needs to call.
+    pub fn to_call(&self) -> u64 {
+        match self.player(self.turn) {
+            Some(player) => self.current_bet().saturating_sub(player.bet),
+            None => 0,
+        }
+    }
+
+    /// Smallest and largest amounts the player in turn can bet or raise to,
+    /// or `None` if they cannot raise.
+    ///
+    /// When the player does not have enough chips for a full raise, both are
+    /// the amount that puts them all in.
+    pub fn raise_range(&self) -> Option<(u64, u64)> {
+        let street = self.street?;
+        let player = self.player(self.turn)?;
+        let current_bet = self.current_bet();
+        let all_in = player.bet + player.chips;
+        if all_in <= current_bet {
+            return None;
+        }
+        let full_raise = self.full_raise(street);
+        if self.acted_at[self.turn].is_some_and(|bet| current_bet < bet + full_raise) {
+            return None;
+        }
+        let min = current_bet + full_raise;
+        let max = match self.options.betting {
+            BettingStructure::NoLimit => u64::MAX,
+            BettingStructure::PotLimit => {
+                let pot_after_call = self.pot + self.bets() + current_bet - player.bet;
+                current_bet + pot_after_call
+            }
+            BettingStructure::FixedLimit { max_raises, .. } => {
+                if self.raises >= max_raises {
+                    return None;
+                }
+                min
+            }
+            BettingStructure::SpreadLimit { max_bet, .. } => current_bet.saturating_add(max_bet),
+        };
+        Some((min.min(all_in), max.min(all_in)))
+    }
+
+    /// Smallest increase over the current bet that counts as a full raise.
+    ///
+    /// This is the size of the previous bet or raise on the street, and at least the big blind.
+    fn full_raise(&self, street: Street) -> u64 {
+        match self.options.betting {
+            BettingStructure::NoLimit | BettingStructure::PotLimit => self.last_raise,
+            BettingStructure::FixedLimit {
+                small_bet, big_bet, ..
+            } => match street {
+                Street::Preflop | Street::Flop => small_bet,
+                Street::Turn | Street::River => big_bet,
+            },
+            BettingStructure::SpreadLimit { min_bet, .. } => self.last_raise.max(min_bet),
+        }
+    }
+
+    fn bets(&self) -> u64 {
+        self.seats.iter().flatten().map(|p| p.bet).sum()
+    }
+
     pub fn deal_to_board(&mut self, num_cards: usize) {
         self.board.append(&mut self.dealer.deal(num_cards))
     }
 
+    /// Deals cards to every player dealt into the hand, and mucks the cards of everyone else.
     pub fn deal_to_players(&mut self, num_cards: usize) {
-        for player_option in &mut self.seats {
-            if let Some(player) = player_option {
-                player.take_cards(self.dealer.deal(num_cards))
+        let waiting = self.waiting;
+        for position in 0..self.seats.len() {
+            if self.dealt_in(position) {
+                let cards = self.dealer.deal(num_cards);
+                self.emit(GameEvent::CardsDealt {
+                    position,
+                    cards: Some(cards.clone()),
+                });
+                let player = self.get_mut_player(position);
+                player.take_cards(cards);
+                player.status = Status::Active;
+            } else if let Some(player) = self.seats[position].as_mut() {
+                player.muck();
+                // A player waiting for the big blind is dealt into the next hand.
+                player.status = match waiting == Some(position) {
+                    true => Status::Folded,
+                    false => Status::SittingOut,
+                };
             }
         }
     }
 
+    /// Moves the bets on the current street into the pot.
     pub fn rake_bets(&mut self) {
-        for player_option in &mut self.seats {
-            if let Some(player) = player_option {
+        for (position, seat) in self.seats.iter_mut().enumerate() {
+            if let Some(player) = seat {
                 self.pot += player.bet;
+                self.contributions[position] += player.bet;
                 player.bet = 0;
             }
         }
     }
 
-    // MAKE AN ITERATOR OF INDICES INSTEAD??
-    // PERHAPS INSREAD OF HANDING OUT MUTABLE REFERENCES - MAKE A FUNCTION TO DO THE MUTATION
-    fn position_at_or_next_to(&mut self, position: usize) -> usize {
-        if let Some(Some(_)) = self.seats.get(position) {
-            return position;
+    /// Gives back the part of the highest bet that no one else matched.
+    fn return_uncalled_bet(&mut self) {
+        let mut bets: Vec<(u64, usize)> = self
+            .seats
+            .iter()
+            .enumerate()
+            .filter_map(|(position, seat)| seat.as_ref().map(|p| (p.bet, position)))
+            .collect();
+        bets.sort_unstable_by(|a, b| b.cmp(a));
+        if let [(highest, position), (second, _), ..] = bets[..] {
+            let player = self.get_mut_player(position);
+            player.bet -= highest - second;
+            player.chips += highest - second;
+            if player.status == Status::AllIn && player.chips > 0 {
+                player.status = Status::Active;
+            }
+            self.record(position, Transaction::Returned(highest - second));
+            if highest > second {
+                self.emit(GameEvent::BetReturned {
+                    position,
+                    chips: highest - second,
+                });
+                if let Some(history) = self.history.as_mut() {
+                    history.uncalled = Some((position, highest - second));
+                }
+            }
+        }
+    }
+
+    /// Pots built from each seat's contributions, with the positions that can win them.
+    ///
+    /// Players who are all in can only win as much from each opponent as they put in,
+    /// so every distinct amount put in by a player still in the hand starts a new side pot.
+    fn pots(&self) -> Vec<(u64, Vec<usize>)> {
+        let in_hand: Vec<usize> = self.positions_from_button(|p| self.in_hand(p));
+        let mut levels: Vec<u64> = in_hand.iter().map(|&p| self.contributions[p]).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        let mut pots: Vec<(u64, Vec<usize>)> = Vec::new();
+        let mut previous = 0;
+        for level in levels {
+            let amount: u64 = self
+                .contributions
+                .iter()
+                .map(|&c| c.min(level) - c.min(previous))
+                .sum();
+            let eligible = in_hand
+                .iter()
+                .copied()
+                .filter(|&p| self.contributions[p] >= level)
+                .collect();
+            pots.push((amount, eligible));
+            previous = level;
+        }
+        // Chips from folded players above the highest level.
+        let contributed: u64 = self.contributions.iter().sum();
+        let awarded: u64 = pots.iter().map(|(amount, _)| amount).sum();
+        if let Some((amount, _)) = pots.last_mut() {
+            *amount += contributed - awarded;
+        }
+        // Dead money, such as a big blind ante, goes to the main pot.
+        if let Some((amount, _)) = pots.first_mut() {
+            *amount += self.pot - contributed;
         }
-        self.position_next_to(position)
+        pots
     }
 
-    fn position_next_to(&mut self, mut position: usize) -> usize {
-        let initial = position;
-        loop {
-            position = (position + 1) % self.seats.len();
-            if let Some(Some(_)) = self.seats.get(position) {
-                return position;
+    /// Awards each pot to the best hands among the players who can win it.
+    ///
+    /// Hands are ranked according to the game variant. When a pot is split,
+    /// odd chips go to the winners closest to the left of the button.
+    fn award_pots(&mut self) {
+        let variant = self.options.variant;
+        let hands: Vec<Option<Hand>> = (0..self.seats.len())
+            .map(|position| {
+                let player = self.player(position)?;
+                let cards: [Card; 7] = [&player.cards[..], &self.board[..]]
+                    .concat()
+                    .try_into()
+                    .ok()?;
+                Some(variant.best_hand(cards))
+            })
+            .collect();
+        let mut rake = match self.replayed_rake.take() {
+            Some(rake) => rake.min(self.pot),
+            None => self.rake(),
+        };
+        self.ledger.record_rake(self.hands, rake);
+        let in_hand: Vec<usize> = (0..self.seats.len()).filter(|&p| self.in_hand(p)).collect();
+        let showdown: Vec<(usize, Hand)> = match in_hand.len() > 1 {
+            true => in_hand
+                .into_iter()
+                .filter_map(|p| Some((p, hands[p]?)))
+                .collect(),
+            false => Vec::new(),
+        };
+        for &(position, hand) in &showdown {
+            let cards = self.get_mut_player(position).cards.clone();
+            self.emit(GameEvent::Showdown {
+                position,
+                cards,
+                hand,
+            });
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.rake = rake;
+            history.board = self.board.clone();
+            history.showdown = showdown;
+        }
+        for (pot, (mut amount, eligible)) in self.pots().into_iter().enumerate() {
+            let taken = rake.min(amount);
+            amount -= taken;
+            rake -= taken;
+            let mut best: Option<&Hand> = None;
+            let mut winners = Vec::new();
+            for position in eligible {
+                let Some(hand) = &hands[position] else {
+                    // The hand was not contested, so the board may be incomplete.
+                    winners.push(position);
+                    continue;
+                };
+                match best.map(|best| variant.cmp_hands(hand, best)) {
+                    None | Some(Greater) => {
+                        best = Some(hand);
+                        winners = vec![position];
+                    }
+                    Some(Equal) => winners.push(position),
+                    Some(Less) => (),
+                }
             }
-            if initial == position {
-                panic!("No players found!")
+            let share = amount / winners.len() as u64;
+            let mut remainder = amount % winners.len() as u64;
+            for &position in &winners {
+                let odd_chip = u64::from(remainder > 0);
+                remainder -= odd_chip;
+                self.get_mut_player(position).chips += share + odd_chip;
+                self.record(position, Transaction::Award(share + odd_chip));
+                let award = Award {
+                    position,
+                    pot,
+                    chips: share + odd_chip,
+                };
+                self.emit(GameEvent::PotAwarded(award));
+                if let Some(history) = self.history.as_mut() {
+                    history.awards.push(award);
+                }
             }
         }
+        self.pot = 0;
+    }
+
+    /// The house's share of the pot, taken from the main pot first.
+    fn rake(&self) -> u64 {
+        let rake = &self.options.rake;
+        if rake.no_flop_no_drop && self.board.is_empty() {
+            return 0;
+        }
+        let amount = self.pot * rake.basis_points / 10_000;
+        let cap = rake
+            .caps
+            .iter()
+            .filter(|&&(players, _)| players <= self.hand_players)
+            .max_by_key(|&&(players, _)| players);
+        match cap {
+            Some(&(_, cap)) => amount.min(cap),
+            None => amount,
+        }
+    }
+
+    /// Positions matching the predicate, starting from the left of the button.
+    fn positions_from_button(&self, predicate: impl Fn(usize) -> bool) -> Vec<usize> {
+        let n = self.seats.len();
+        (1..=n)
+            .map(|offset| (self.button + offset) % n)
+            .filter(|&position| predicate(position))
+            .collect()
+    }
+
+    fn status(&self, position: usize) -> Option<Status> {
+        self.player(position).map(|player| player.status)
+    }
+
+    /// Whether the player in this position has not folded.
+    fn in_hand(&self, position: usize) -> bool {
+        matches!(self.status(position), Some(Status::Active | Status::AllIn))
+    }
+
+    /// Whether the player in this position is in the hand and has chips left to bet.
+    fn can_act(&self, position: usize) -> bool {
+        self.status(position) == Some(Status::Active)
+    }
+
+    /// Whether the player in this position will be dealt into the next hand.
+    fn dealt_in(&self, position: usize) -> bool {
+        self.waiting != Some(position)
+            && self
+                .player(position)
+                .is_some_and(|player| player.status != Status::SittingOut && player.chips > 0)
+    }
+
+    /// Next position after this one, going around the table, whose player will be dealt in.
+    fn next_dealt_in(&self, position: usize) -> usize {
+        let n = self.seats.len();
+        (1..=n)
+            .map(|offset| (position + offset) % n)
+            .find(|&position| self.dealt_in(position))
+            .expect("No players found!")
+    }
+
+    /// Previous position before this one, going around the table, whose player will be dealt in.
+    fn previous_dealt_in(&self, position: usize) -> usize {
+        let n = self.seats.len();
+        (1..=n)
+            .map(|offset| (position + n - offset) % n)
+            .find(|&position| self.dealt_in(position))
+            .expect("No players found!")
     }
 
     fn get_mut_player(&mut self, position: usize) -> &mut Player {
         self.seats.get_mut(position).unwrap().as_mut().unwrap()
     }
 
-    pub fn start_hand(&mut self) -> Result<&mut Player, String> {
+    /// Moves the button and blinds for a new hand, returning the small and big
+    /// blind positions. The small blind is dead when its player is not dealt in.
+    ///
+    /// Uses the dead button rule: the big blind always moves to the next player,
+    /// the small blind goes to whoever had the big blind, and the button goes to
+    /// the seat that had the small blind, even if it is now empty. This way no
+    /// one skips or pays the big blind twice when players leave or join.
+    ///
+    /// Heads up, the button posts the small blind. A player who owes a big blind
+    /// is not dealt in on the button, see `start_hand`.
+    /// Players skipped by the blinds while sitting out owe them when they come back.
+    fn move_blinds(&mut self, num_dealt_in: usize) -> (usize, usize) {
+        let Some((last_small_blind, last_big_blind)) = self.blinds else {
+            // First hand
+            if !self.dealt_in(self.button) {
+                self.button = self.next_dealt_in(self.button);
+            }
+            let small_blind = match num_dealt_in {
+                2 => self.button,
+                _ => self.next_dealt_in(self.button),
+            };
+            return (small_blind, self.next_dealt_in(small_blind));
+        };
+        let big_blind = self.next_dealt_in(last_big_blind);
+        let n = self.seats.len();
+        for offset in 1..((big_blind + n - last_big_blind) % n) {
+            if let Some(player) = self.seats[(last_big_blind + offset) % n].as_mut() {
+                player.missed_small_blind = true;
+                player.missed_big_blind = true;
+            }
+        }
+        if num_dealt_in == 2 {
+            self.button = self.next_dealt_in(big_blind);
+            return (self.button, big_blind);
+        }
+        self.button = last_small_blind;
+        if self.button == big_blind {
+            // Heads up the button posts the small blind, so when a third player
+            // joins, the last small blind can be next to post the big blind.
+            // The button goes to the new player, who then waits a hand.
+            self.button = self.previous_dealt_in(last_big_blind);
+        }
+        if let Some(player) = self.seats[last_big_blind].as_mut() {
+            player.missed_small_blind |= player.status == Status::SittingOut || player.chips == 0;
+        }
+        (last_big_blind, big_blind)
+    }
+
+    /// Deals a new hand, posts the blinds and gives the turn to the first player to act.
+    ///
+    /// Players sitting out or without chips are not dealt in.
+    pub fn start_hand(&mut self) -> Result<(), String> {
         let small_blind = self.options.small_blind;
         let big_blind = self.options.big_blind;
-        if self.num_players < 2 {
+        let big_blind_ante = self.options.big_blind_ante.then_some(self.options.ante);
+        if self.street.is_some() {
+            return Err(String::from("Hand is already in progress"));
+        }
+        self.waiting = None;
+        let mut num_dealt_in = (0..self.seats.len()).filter(|&p| self.dealt_in(p)).count();
+        if num_dealt_in < 2 {
             return Err(String::from("Not enough players"));
         }
-        // Heads up
-        let mut position = if self.num_players == 2 {
-            self.position_at_or_next_to(self.button)
+        self.hands += 1;
+        let (small_blind_position, big_blind_position) = match self.replayed_blinds.take() {
+            Some(blinds) => blinds,
+            None => {
+                let (mut small_blind, mut big_blind) = self.move_blinds(num_dealt_in);
+                let owes_big_blind = self.player(self.button).is_some_and(|p| p.missed_big_blind);
+                if num_dealt_in > 2 && self.dealt_in(self.button) && owes_big_blind {
+                    // Rather than post on the button and then post the big blind
+                    // again next hand, they wait for the big blind to reach them.
+                    self.waiting = Some(self.button);
+                    num_dealt_in -= 1;
+                    (small_blind, big_blind) = self.move_blinds(num_dealt_in);
+                }
+                (Some(small_blind), big_blind)
+            }
+        };
+        self.hand_players = num_dealt_in;
+        self.blinds = Some((
+            small_blind_position.unwrap_or_else(|| self.previous_dealt_in(big_blind_position)),
+            big_blind_position,
+        ));
+        let seed = match self.stacked_deck.take() {
+            Some(deck) => {
+                self.dealer = Dealer::stacked(deck);
+                None
+            }
+            None => {
+                let seed = self.rng.gen();
+                self.dealer = Dealer::with_seed(self.options.variant.deck(), seed);
+                Some(seed)
+            }
+        };
+        self.board.clear();
+        self.contributions.fill(0);
+        self.emit(GameEvent::HandStarted {
+            number: self.hands,
+            button: self.button,
+        });
+        self.deal_to_players(2);
+        self.history = Some(self.new_history(seed));
+        self.street = Some(Street::Preflop);
+        self.reset_round();
+        if !self.options.big_blind_ante {
+            let ante = self.options.ante;
+            for position in 0..self.seats.len() {
+                if self.status(position) == Some(Status::Active) {
+                    let ante = self.get_mut_player(position).bet(ante);
+                    self.record_post(position, PostKind::Ante, ante);
+                }
+            }
+            self.rake_bets();
+        }
+
+        if let Some(position) = small_blind_position.filter(|&p| self.in_hand(p)) {
+            let player = self.get_mut_player(position);
+            let blind = player.bet(small_blind);
+            player.missed_small_blind = false;
+            self.record_post(position, PostKind::SmallBlind, blind);
+        }
+
+        let player = self.get_mut_player(big_blind_position);
+        let blind = player.bet(big_blind);
+        player.missed_small_blind = false;
+        player.missed_big_blind = false;
+        self.record_post(big_blind_position, PostKind::BigBlind, blind);
+        if let Some(ante) = big_blind_ante {
+            let ante = self.get_mut_player(big_blind_position).post_dead(ante);
+            self.pot += ante;
+            self.record_post(big_blind_position, PostKind::Ante, ante);
+        }
+        // The big blind counts as the first bet.
+        self.raises = 1;
+
+        // Missed big blinds are live, missed small blinds are dead.
+        for position in self.positions_from_button(|p| self.in_hand(p)) {
+            let player = self.get_mut_player(position);
+            let live = match player.missed_big_blind {
+                true => player.bet(big_blind.saturating_sub(player.bet)),
+                false => 0,
+            };
+            let dead = match player.missed_small_blind {
+                true => player.post_dead(small_blind),
+                false => 0,
+            };
+            player.missed_small_blind = false;
+            player.missed_big_blind = false;
+            self.pot += dead;
+            if live > 0 {
+                self.record_post(position, PostKind::BigBlind, live);
+            }
+            self.record_post(position, PostKind::Dead, dead);
+        }
+
+        self.turn = big_blind_position;
+        if let Some(position) = self.straddler(big_blind_position, num_dealt_in) {
+            let player = self.get_mut_player(position);
+            let straddle = player.bet(2 * big_blind);
+            if player.bet == 2 * big_blind {
+                self.last_raise = 2 * big_blind;
+            }
+            self.record_post(position, PostKind::Straddle, straddle);
+            self.raises += 1;
+            self.turn = position;
+        }
+        self.next_turn();
+        self.debug_check_chips();
+        Ok(())
+    }
+
+    fn new_history(&self, seed: Option<u64>) -> HandHistory {
+        let seated = self
+            .seats
+            .iter()
+            .enumerate()
+            .filter_map(|(position, seat)| Some((position, seat.as_ref()?)));
+        HandHistory {
+            number: self.hands,
+            seed,
+            time: match self.clock {
+                Some(clock) => clock(),
+                None => SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |time| time.as_secs()),
+            },
+            options: self.options.clone(),
+            seats: seated
+                .clone()
+                .map(|(position, player)| Seat {
+                    position,
+                    id: player.id,
+                    name: player.name.clone(),
+                    chips: player.chips,
+                    dealt_in: player.status == Status::Active,
+                })
+                .collect(),
+            button: self.button,
+            posts: Vec::new(),
+            hole_cards: seated
+                .filter(|(_, player)| player.status == Status::Active)
+                .map(|(position, player)| (position, player.cards.clone()))
+                .collect(),
+            actions: Vec::new(),
+            board: Vec::new(),
+            uncalled: None,
+            showdown: Vec::new(),
+            awards: Vec::new(),
+            rake: 0,
+        }
+    }
+
+    fn straddler(&self, big_blind_position: usize, num_dealt_in: usize) -> Option<usize> {
+        if num_dealt_in < 3 {
+            return None;
+        }
+        match self.options.straddle {
+            Straddle::Off => None,
+            Straddle::UnderTheGun => Some(self.next_dealt_in(big_blind_position)),
+            Straddle::Button if self.in_hand(self.button) => Some(self.button),
+            Straddle::Button => None,
+        }
+    }
+
+    /// Stops dealing the player in. They owe any blinds they miss when they come back.
+    ///
+    /// Players still in a hand have to fold before they can sit out.
+    pub fn sit_out(&mut self, position: usize) -> Result<(), String> {
+        if self.street.is_some() && self.in_hand(position) {
+            return Err(format!("Player in seat {position} is still in the hand"));
+        }
+        match self.seats.get_mut(position) {
+            Some(Some(player)) => player.status = Status::SittingOut,
+            _ => return Err(format!("Seat {position} is empty")),
+        }
+        Ok(())
+    }
+
+    /// Deals the player in again from the next hand.
+    pub fn sit_in(&mut self, position: usize) -> Result<(), String> {
+        match self.seats.get_mut(position) {
+            Some(Some(player)) if player.chips == 0 => {
+                Err(format!("Player in seat {position} has no chips"))
+            }
+            Some(Some(player)) => {
+                if player.status == Status::SittingOut {
+                    player.status = Status::Active;
+                }
+                Ok(())
+            }
+            _ => Err(format!("Seat {position} is empty")),
+        }
+    }
+
+    /// Folds for the player whose turn it is when they run out of time, and sits them out.
+    pub fn timeout(&mut self) -> Result<(), String> {
+        let position = self.turn;
+        self.act(Action::Fold)?;
+        self.get_mut_player(position).status = Status::SittingOut;
+        Ok(())
+    }
+
+    /// Takes an action for the player whose turn it is.
+    ///
+    /// When the betting round is complete the next street is dealt, and after
+    /// the river, or when only one player is left, the pots are awarded.
+    pub fn act(&mut self, action: Action) -> Result<(), String> {
+        if self.street.is_none() {
+            return Err(String::from("No hand in progress"));
+        }
+        let position = self.turn;
+        let current_bet = self.current_bet();
+        let to_call = self.to_call();
+        let player = self.get_mut_player(position);
+        let all_in = player.bet + player.chips;
+        let chips = player.chips;
+        match action {
+            Action::Fold => player.fold(),
+            Action::Check if to_call > 0 => {
+                return Err(format!("Cannot check, {to_call} to call"));
+            }
+            Action::Check => (),
+            Action::Call if to_call == 0 => {
+                return Err(String::from("Nothing to call"));
+            }
+            Action::Call => {
+                player.bet(to_call);
+            }
+            Action::Bet(_) if current_bet > 0 => {
+                return Err(format!(
+                    "Cannot bet, the bet is {current_bet}. Raise instead"
+                ));
+            }
+            Action::Raise(_) if current_bet == 0 => {
+                return Err(String::from("Cannot raise, nobody has bet. Bet instead"));
+            }
+            Action::Bet(amount) | Action::Raise(amount) => self.raise_to(amount)?,
+            Action::AllIn if all_in <= current_bet => {
+                player.bet(player.chips);
+            }
+            Action::AllIn => self.raise_to(all_in)?,
+        }
+        let player = self.get_mut_player(position);
+        let (bet, all_in) = (chips - player.chips, player.status == Status::AllIn);
+        let action = match action {
+            Action::AllIn if self.current_bet() <= current_bet => Action::Call,
+            Action::AllIn if current_bet == 0 => Action::Bet(self.current_bet()),
+            Action::AllIn => Action::Raise(self.current_bet()),
+            action => action,
+        };
+        self.record(position, Transaction::Bet(bet));
+        if let Some(street) = self.street {
+            let record = ActionRecord {
+                street,
+                position,
+                action,
+                chips: bet,
+                all_in,
+            };
+            self.emit(GameEvent::ActionTaken(record));
+            if let Some(history) = self.history.as_mut() {
+                history.actions.push(record);
+            }
+        }
+        self.acted[position] = true;
+        self.acted_at[position] = Some(self.current_bet());
+        if self.round_complete() {
+            self.end_round();
         } else {
-            self.position_next_to(self.button)
+            self.next_turn();
+        }
+        self.debug_check_chips();
+        Ok(())
+    }
+
+    fn raise_to(&mut self, amount: u64) -> Result<(), String> {
+        let Some((min, max)) = self.raise_range() else {
+            return Err(String::from("Cannot raise"));
         };
+        if amount < min || amount > max {
+            return Err(match min == max {
+                true => format!("Bet must be {min}"),
+                false => format!("Bet must be between {min} and {max}"),
+            });
+        }
+        let increase = amount - self.current_bet();
+        if let Some(street) = self.street {
+            if increase >= self.full_raise(street) {
+                self.last_raise = increase;
+            }
+        }
+        let player = self.get_mut_player(self.turn);
+        player.bet(amount - player.bet);
+        self.raises += 1;
+        self.acted.fill(false);
+        Ok(())
+    }
 
-        let mut player = self.get_mut_player(position);
-        player.bet(small_blind);
+    fn reset_round(&mut self) {
+        self.acted.fill(false);
+        self.acted_at.fill(None);
+        self.last_raise = self.options.big_blind;
+        self.raises = 0;
+    }
 
-        position = self.position_next_to(position);
-        player = self.get_mut_player(position);
-        player.bet(big_blind);
+    fn round_complete(&self) -> bool {
+        let current_bet = self.current_bet();
+        let in_hand = self.positions_from_button(|p| self.in_hand(p));
+        let can_act = self.positions_from_button(|p| self.can_act(p));
+        if in_hand.len() <= 1 {
+            return true;
+        }
+        let bet = |p: usize| self.seats[p].as_ref().unwrap().bet;
+        // No one left to bet against.
+        if can_act.len() <= 1 && can_act.iter().all(|&p| bet(p) >= current_bet) {
+            return true;
+        }
+        can_act
+            .iter()
+            .all(|&p| self.acted[p] && bet(p) == current_bet)
+    }
 
-        self.turn = self.position_next_to(position);
-        Ok(self.get_mut_player(self.turn))
+    /// Collects the bets and deals the next street, running out the board when
+    /// no more betting is possible.
+    fn end_round(&mut self) {
+        self.return_uncalled_bet();
+        self.rake_bets();
+        self.reset_round();
+        let contested = self.positions_from_button(|p| self.in_hand(p)).len() > 1;
+        while contested && self.street < Some(Street::River) {
+            let (street, cards) = match self.street {
+                Some(Street::Preflop) => (Street::Flop, 3),
+                Some(Street::Flop) => (Street::Turn, 1),
+                _ => (Street::River, 1),
+            };
+            self.deal_to_board(cards);
+            self.street = Some(street);
+            self.emit(GameEvent::StreetAdvanced {
+                street,
+                cards: self.board[self.board.len() - cards..].to_vec(),
+            });
+            if self.positions_from_button(|p| self.can_act(p)).len() > 1 {
+                self.turn = self.button;
+                self.next_turn();
+                return;
+            }
+        }
+        self.finish_hand();
     }
 
-    pub fn next_hand(&mut self) {
-        self.dealer = Dealer::new();
-        self.board.clear();
-        self.advance_button();
-        self.start_hand();
+    /// Settles the hand in progress now: returns any uncalled bet, awards the
+    /// pots and ends the hand. Returns the positions that won chips.
+    ///
+    /// Hands settle themselves when the betting ends, so this is only needed
+    /// when dealing the board with `deal_to_board`.
+    pub fn showdown(&mut self) -> Result<Vec<usize>, String> {
+        if self.street.is_none() {
+            return Err(String::from("No hand in progress"));
+        }
+        let contested = self.positions_from_button(|p| self.in_hand(p)).len() > 1;
+        if contested && self.board.len() != 5 {
+            return Err(format!(
+                "Showdown needs 5 board cards, board has {}",
+                self.board.len()
+            ));
+        }
+        self.return_uncalled_bet();
+        self.rake_bets();
+        self.reset_round();
+        self.finish_hand();
+        let mut winners: Vec<usize> = self
+            .histories
+            .last()
+            .map(|history| history.awards.iter().map(|award| award.position).collect())
+            .unwrap_or_default();
+        winners.sort_unstable();
+        winners.dedup();
+        Ok(winners)
     }
 
-    pub fn advance_button(&mut self) {
-        let position = self.position_next_to(self.button);
-        self.button = position;
+    /// Awards the pots and records the end of the hand.
+    fn finish_hand(&mut self) {
+        self.award_pots();
+        self.street = None;
+        let busted: Vec<GameEvent> = self
+            .seats
+            .iter()
+            .enumerate()
+            .filter_map(|(position, seat)| {
+                let player = seat.as_ref()?;
+                (player.chips == 0 && player.status != Status::SittingOut).then_some(
+                    GameEvent::Busted {
+                        position,
+                        id: player.id,
+                    },
+                )
+            })
+            .collect();
+        for event in busted {
+            self.emit(event);
+        }
+        self.emit(GameEvent::HandEnded { number: self.hands });
+        self.histories.extend(self.history.take());
     }
 
+    /// Gives the turn to the next player who can act, and returns the player in turn.
     pub fn next_turn(&mut self) -> &mut Player {
-        let position = self.position_next_to(self.turn);
-        self.turn = position;
-        self.get_mut_player(position)
+        let n = self.seats.len();
+        if let Some(position) = (1..=n)
+            .map(|offset| (self.turn + offset) % n)
+            .find(|&position| self.can_act(position))
+        {
+            self.turn = position;
+        }
+        self.get_mut_player(self.turn)
     }
 }
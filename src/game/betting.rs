@@ -0,0 +1,40 @@
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Actions a player can take on their turn.
+///
+/// Bet and raise amounts are the total the player will have bet on the
+/// current street, not the amount added to it.
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// Open the betting on a street where no one has bet.
+    Bet(u64),
+    /// Raise the current bet to the given amount.
+    Raise(u64),
+    /// Put all remaining chips in, as a call or a raise.
+    AllIn,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Limits on the size of bets and raises.
+#[allow(clippy::enum_variant_names)]
+pub enum BettingStructure {
+    /// Bets and raises can be any size up to the player's stack.
+    #[default]
+    NoLimit,
+    /// Bets and raises can be at most the size of the pot after calling.
+    PotLimit,
+    /// Bets and raises are a fixed size, with a limited number per street.
+    ///
+    /// The small bet is used preflop and on the flop, the big bet on the turn and river.
+    /// The big blind counts as the first bet preflop.
+    FixedLimit {
+        small_bet: u64,
+        big_bet: u64,
+        max_raises: u32,
+    },
+    /// Bets and raises can be any size within the range.
+    SpreadLimit { min_bet: u64, max_bet: u64 },
+}
@@ -0,0 +1,68 @@
+use super::{
+    history::{ActionRecord, Award, Post},
+    Street,
+};
+use crate::{card::Card, hand::Hand};
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Something that happened at the table.
+///
+/// Once enabled with `Game::set_events_enabled`, events are queued in the order
+/// they happen and collected with `Game::take_events`.
+pub enum GameEvent {
+    Seated {
+        position: usize,
+        id: u64,
+        chips: u64,
+    },
+    /// A player left the table, cashing out their chips.
+    Left {
+        position: usize,
+        id: u64,
+        chips: u64,
+    },
+    HandStarted {
+        number: u64,
+        button: usize,
+    },
+    /// Hole cards dealt to a player, or `None` if hidden from the viewer.
+    CardsDealt {
+        position: usize,
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::card::as_string::option_cards")
+        )]
+        cards: Option<Vec<Card>>,
+    },
+    /// An ante or blind posted before the betting.
+    Posted(Post),
+    ActionTaken(ActionRecord),
+    /// Part of a bet that no one called was given back.
+    BetReturned {
+        position: usize,
+        chips: u64,
+    },
+    /// The betting moved to a new street, with the cards dealt to the board for it.
+    StreetAdvanced {
+        street: Street,
+        #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
+        cards: Vec<Card>,
+    },
+    /// A player still in at the end of the hand showed their cards.
+    Showdown {
+        position: usize,
+        #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
+        cards: Vec<Card>,
+        hand: Hand,
+    },
+    PotAwarded(Award),
+    /// A player lost all their chips in the hand.
+    Busted {
+        position: usize,
+        id: u64,
+    },
+    HandEnded {
+        number: u64,
+    },
+}
@@ -0,0 +1,104 @@
+use super::{betting::Action, GameOptions, Street};
+use crate::{card::Card, hand::Hand};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Everything that happened in one hand, recorded as it was played.
+pub struct HandHistory {
+    /// Number of the hand at its table, starting from 1.
+    pub number: u64,
+    /// Seed the deck was shuffled with, or `None` if it was stacked or the hand was imported.
+    pub seed: Option<u64>,
+    /// Seconds since the Unix epoch when the hand started.
+    pub time: u64,
+    pub options: GameOptions,
+    /// Players seated when the hand started, with their stacks before posting.
+    pub seats: Vec<Seat>,
+    pub button: usize,
+    pub posts: Vec<Post>,
+    /// Cards dealt to each position.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::card::as_string::position_cards")
+    )]
+    pub hole_cards: Vec<(usize, Vec<Card>)>,
+    pub actions: Vec<ActionRecord>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
+    pub board: Vec<Card>,
+    /// Part of a bet that no one called, given back to the player who made it.
+    pub uncalled: Option<(usize, u64)>,
+    /// Hands of the players still in at showdown. Empty if everyone else folded.
+    pub showdown: Vec<(usize, Hand)>,
+    pub awards: Vec<Award>,
+    pub rake: u64,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seat {
+    pub position: usize,
+    pub id: u64,
+    pub name: String,
+    pub chips: u64,
+    /// Whether the player was dealt in.
+    pub dealt_in: bool,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PostKind {
+    Ante,
+    SmallBlind,
+    BigBlind,
+    Straddle,
+    /// A missed small blind, which goes into the pot without counting as a bet.
+    Dead,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Post {
+    pub position: usize,
+    pub kind: PostKind,
+    pub chips: u64,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionRecord {
+    pub street: Street,
+    pub position: usize,
+    /// The action as it was taken, with all ins recorded as the call, bet or raise they made.
+    pub action: Action,
+    /// Chips the player put in.
+    pub chips: u64,
+    /// Whether the action put the player all in.
+    pub all_in: bool,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Award {
+    pub position: usize,
+    /// 0 for the main pot, then each side pot in order.
+    pub pot: usize,
+    pub chips: u64,
+}
+
+impl HandHistory {
+    /// Actions taken on the given street.
+    pub fn actions_on(&self, street: Street) -> impl Iterator<Item = &ActionRecord> {
+        self.actions
+            .iter()
+            .filter(move |action| action.street == street)
+    }
+
+    /// Total chips won by the player in this position.
+    pub fn won(&self, position: usize) -> u64 {
+        self.awards
+            .iter()
+            .filter(|award| award.position == position)
+            .map(|award| award.chips)
+            .sum()
+    }
+}
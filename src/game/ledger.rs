@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Chips moving between a player and the table.
+pub enum Transaction {
+    /// Chips the player sat down with.
+    BuyIn(u64),
+    /// Chips added after the player went broke.
+    Rebuy(u64),
+    /// Chips added to a stack that was not empty.
+    TopUp(u64),
+    /// Chips the player left the table with.
+    CashOut(u64),
+    Ante(u64),
+    /// Small, big, straddle and missed blinds, live or dead.
+    Blind(u64),
+    /// Chips put in by calling, betting or raising.
+    Bet(u64),
+    /// Part of a bet that no one called.
+    Returned(u64),
+    /// Chips won from a pot.
+    Award(u64),
+}
+
+impl Transaction {
+    pub fn chips(self) -> u64 {
+        match self {
+            Transaction::BuyIn(chips)
+            | Transaction::Rebuy(chips)
+            | Transaction::TopUp(chips)
+            | Transaction::CashOut(chips)
+            | Transaction::Ante(chips)
+            | Transaction::Blind(chips)
+            | Transaction::Bet(chips)
+            | Transaction::Returned(chips)
+            | Transaction::Award(chips) => chips,
+        }
+    }
+
+    /// Whether the chips are added to the player's stack.
+    pub fn is_credit(self) -> bool {
+        matches!(
+            self,
+            Transaction::BuyIn(_)
+                | Transaction::Rebuy(_)
+                | Transaction::TopUp(_)
+                | Transaction::Returned(_)
+                | Transaction::Award(_)
+        )
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// Number of hands started when the transaction happened.
+    pub hand: u64,
+    /// Id of the player.
+    pub player: u64,
+    pub transaction: Transaction,
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Record of every transaction at a table.
+pub struct Ledger {
+    entries: Vec<Entry>,
+    /// Rake taken by the house, by hand.
+    rake: Vec<(u64, u64)>,
+    /// Running total of each player's transactions.
+    balances: BTreeMap<u64, i64>,
+}
+
+impl Ledger {
+    /// Adds a transaction, unless it is for no chips.
+    pub fn record(&mut self, hand: u64, player: u64, transaction: Transaction) {
+        if transaction.chips() == 0 {
+            return;
+        }
+        let chips = transaction.chips() as i64;
+        *self.balances.entry(player).or_default() += match transaction.is_credit() {
+            true => chips,
+            false => -chips,
+        };
+        self.entries.push(Entry {
+            hand,
+            player,
+            transaction,
+        });
+    }
+
+    pub fn record_rake(&mut self, hand: u64, chips: u64) {
+        if chips > 0 {
+            self.rake.push((hand, chips));
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Transactions during the given hand.
+    pub fn hand(&self, hand: u64) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |entry| entry.hand == hand)
+    }
+
+    /// Total chips brought to the table.
+    pub fn bought_in(&self) -> u64 {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry.transaction {
+                Transaction::BuyIn(chips)
+                | Transaction::Rebuy(chips)
+                | Transaction::TopUp(chips) => Some(chips),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Total chips taken from the table.
+    pub fn cashed_out(&self) -> u64 {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry.transaction {
+                Transaction::CashOut(chips) => Some(chips),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Total rake taken by the house.
+    pub fn rake(&self) -> u64 {
+        self.rake.iter().map(|&(_, chips)| chips).sum()
+    }
+
+    /// Rake taken from the given hand.
+    pub fn rake_for(&self, hand: u64) -> u64 {
+        self.rake
+            .iter()
+            .filter(|&&(h, _)| h == hand)
+            .map(|&(_, chips)| chips)
+            .sum()
+    }
+
+    /// Chips the player should have in their stack according to the ledger.
+    pub fn balance(&self, player: u64) -> i64 {
+        self.balances.get(&player).copied().unwrap_or(0)
+    }
+}
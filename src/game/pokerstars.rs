@@ -0,0 +1,868 @@
+//! Hand histories in the text format written by PokerStars, which most
+//! trackers and replayers can read, and which most other sites can export.
+
+use std::fmt::Write;
+
+use super::{
+    betting::{Action, BettingStructure},
+    history::{ActionRecord, Award, HandHistory, Post, PostKind, Seat},
+    GameOptions, Rake, Straddle, Street,
+};
+use crate::{
+    card::{format_cards, parse_cards, Card},
+    hand::Variant,
+};
+
+/// Writes the hands one after another, separated by blank lines.
+pub fn write_hands(histories: &[HandHistory], table: &str, hero: Option<usize>) -> String {
+    histories
+        .iter()
+        .map(|history| write_hand(history, table, hero))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Writes one hand. Like a real export, only the hole cards of the hero's
+/// seat are dealt face up, unless there is no hero.
+pub fn write_hand(history: &HandHistory, table: &str, hero: Option<usize>) -> String {
+    let mut out = String::new();
+    // Writing to a string cannot fail.
+    write_lines(&mut out, history, table, hero).unwrap();
+    out
+}
+
+fn write_lines(
+    out: &mut String,
+    history: &HandHistory,
+    table: &str,
+    hero: Option<usize>,
+) -> std::fmt::Result {
+    let options = &history.options;
+    let name = |position: usize| {
+        history
+            .seats
+            .iter()
+            .find(|seat| seat.position == position)
+            .map_or("", |seat| seat.name.as_str())
+    };
+    let blinds = format!("{}/{}", options.small_blind, options.big_blind);
+    let stakes = match options.betting {
+        BettingStructure::FixedLimit {
+            small_bet, big_bet, ..
+        } => format!("{small_bet}/{big_bet}"),
+        BettingStructure::SpreadLimit { min_bet, max_bet } => {
+            format!("{blinds} spread {min_bet}-{max_bet}")
+        }
+        _ => blinds,
+    };
+    writeln!(
+        out,
+        "PokerStars Hand #{}:  {} ({stakes}) - {}",
+        history.number,
+        game_name(options),
+        format_time(history.time)
+    )?;
+    writeln!(
+        out,
+        "Table '{table}' {}-max Seat #{} is the button",
+        options.num_seats,
+        history.button + 1
+    )?;
+    for seat in &history.seats {
+        let sitting_out = match seat.dealt_in {
+            true => "",
+            false => " is sitting out",
+        };
+        writeln!(
+            out,
+            "Seat {}: {} ({} in chips){sitting_out}",
+            seat.position + 1,
+            seat.name,
+            seat.chips
+        )?;
+    }
+
+    let mut bets = vec![0; options.num_seats];
+    for post in &history.posts {
+        let dead = history
+            .posts
+            .iter()
+            .find(|p| p.position == post.position && p.kind == PostKind::Dead);
+        let (text, chips) = match (post.kind, dead) {
+            (PostKind::Ante, _) => ("the ante", post.chips),
+            (PostKind::SmallBlind, _) => ("small blind", post.chips),
+            (PostKind::BigBlind, Some(dead)) => ("small & big blinds", post.chips + dead.chips),
+            (PostKind::BigBlind, None) => ("big blind", post.chips),
+            (PostKind::Straddle, _) => ("straddle", post.chips),
+            // Written with the big blind it was posted with.
+            (PostKind::Dead, _) if has_big_blind(history, post.position) => continue,
+            (PostKind::Dead, _) => ("small blind", post.chips),
+        };
+        if !matches!(post.kind, PostKind::Ante | PostKind::Dead) {
+            bets[post.position] += post.chips;
+        }
+        writeln!(out, "{}: posts {text} {chips}", name(post.position))?;
+    }
+
+    let last_street = history.actions.last().map(|action| action.street);
+    let board = &history.board;
+    for street in [Street::Preflop, Street::Flop, Street::Turn, Street::River] {
+        match street {
+            Street::Preflop => {
+                writeln!(out, "*** HOLE CARDS ***")?;
+                let dealt = history
+                    .hole_cards
+                    .iter()
+                    .filter(|(position, _)| hero.is_none_or(|hero| hero == *position));
+                for (position, cards) in dealt {
+                    writeln!(
+                        out,
+                        "Dealt to {} [{}]",
+                        name(*position),
+                        format_cards(cards)
+                    )?;
+                }
+            }
+            Street::Flop if board.len() >= 3 => {
+                writeln!(out, "*** FLOP *** [{}]", format_cards(&board[..3]))?
+            }
+            Street::Turn if board.len() >= 4 => writeln!(
+                out,
+                "*** TURN *** [{}] [{}]",
+                format_cards(&board[..3]),
+                format_cards(&board[3..4])
+            )?,
+            Street::River if board.len() >= 5 => writeln!(
+                out,
+                "*** RIVER *** [{}] [{}]",
+                format_cards(&board[..4]),
+                format_cards(&board[4..5])
+            )?,
+            _ => break,
+        }
+        if street != Street::Preflop {
+            bets.fill(0);
+        }
+        for action in history.actions_on(street) {
+            let current_bet = bets.iter().copied().max().unwrap_or(0);
+            let text = match action.action {
+                Action::Fold => String::from("folds"),
+                Action::Check => String::from("checks"),
+                Action::Call => format!("calls {}", action.chips),
+                Action::Bet(_) => format!("bets {}", action.chips),
+                Action::Raise(to) => format!("raises {} to {to}", to - current_bet),
+                Action::AllIn => format!("goes all in for {}", action.chips),
+            };
+            let all_in = match action.all_in {
+                true => " and is all-in",
+                false => "",
+            };
+            bets[action.position] += action.chips;
+            writeln!(out, "{}: {text}{all_in}", name(action.position))?;
+        }
+        if Some(street) == last_street {
+            if let Some((position, chips)) = history.uncalled {
+                writeln!(out, "Uncalled bet ({chips}) returned to {}", name(position))?;
+            }
+        }
+    }
+
+    let num_pots = history.awards.iter().map(|a| a.pot + 1).max().unwrap_or(0);
+    let pot_name = |pot: usize| match (num_pots, pot) {
+        (1, _) => String::from("pot"),
+        (_, 0) => String::from("main pot"),
+        (2, _) => String::from("side pot"),
+        _ => format!("side pot-{pot}"),
+    };
+    if !history.showdown.is_empty() {
+        writeln!(out, "*** SHOW DOWN ***")?;
+        for (position, hand) in &history.showdown {
+            writeln!(
+                out,
+                "{}: shows [{}] ({hand})",
+                name(*position),
+                format_cards(hole_cards(history, *position))
+            )?;
+        }
+    }
+    for award in &history.awards {
+        writeln!(
+            out,
+            "{} collected {} from {}",
+            name(award.position),
+            award.chips,
+            pot_name(award.pot)
+        )?;
+    }
+    if history.showdown.is_empty() {
+        for award in &history.awards {
+            writeln!(out, "{}: doesn't show hand", name(award.position))?;
+        }
+    }
+
+    writeln!(out, "*** SUMMARY ***")?;
+    let total: u64 = history.awards.iter().map(|a| a.chips).sum::<u64>() + history.rake;
+    writeln!(out, "Total pot {total} | Rake {}", history.rake)?;
+    if !board.is_empty() {
+        writeln!(out, "Board [{}]", format_cards(board))?;
+    }
+    let small_blind = history
+        .posts
+        .iter()
+        .find(|post| post.kind == PostKind::SmallBlind)
+        .map(|post| post.position);
+    let big_blind = history
+        .posts
+        .iter()
+        .find(|post| post.kind == PostKind::BigBlind)
+        .map(|post| post.position);
+    for seat in history.seats.iter().filter(|seat| seat.dealt_in) {
+        let position = seat.position;
+        let mut role = String::new();
+        if position == history.button {
+            role.push_str(" (button)");
+        }
+        if Some(position) == small_blind {
+            role.push_str(" (small blind)");
+        }
+        if Some(position) == big_blind {
+            role.push_str(" (big blind)");
+        }
+        let won = history.won(position);
+        let folded = history
+            .actions
+            .iter()
+            .find(|a| a.position == position && a.action == Action::Fold);
+        let shown = history.showdown.iter().find(|(p, _)| *p == position);
+        let result = match (folded, shown) {
+            (Some(fold), _) => match fold.street {
+                Street::Preflop => String::from("folded before Flop"),
+                Street::Flop => String::from("folded on the Flop"),
+                Street::Turn => String::from("folded on the Turn"),
+                Street::River => String::from("folded on the River"),
+            },
+            (None, Some((_, hand))) => {
+                let cards = format_cards(hole_cards(history, position));
+                match won {
+                    0 => format!("showed [{cards}] and lost with {hand}"),
+                    _ => format!("showed [{cards}] and won ({won}) with {hand}"),
+                }
+            }
+            (None, None) if won > 0 => format!("collected ({won})"),
+            (None, None) => String::from("mucked"),
+        };
+        writeln!(out, "Seat {}: {}{role} {result}", position + 1, seat.name)?;
+    }
+    Ok(())
+}
+
+fn has_big_blind(history: &HandHistory, position: usize) -> bool {
+    history
+        .posts
+        .iter()
+        .any(|post| post.position == position && post.kind == PostKind::BigBlind)
+}
+
+fn hole_cards(history: &HandHistory, position: usize) -> &[Card] {
+    history
+        .hole_cards
+        .iter()
+        .find(|(p, _)| *p == position)
+        .map_or(&[], |(_, cards)| cards)
+}
+
+fn game_name(options: &GameOptions) -> String {
+    let variant = match options.variant {
+        Variant::Standard => "Hold'em",
+        Variant::ShortDeck => "6+ Hold'em",
+    };
+    let betting = match options.betting {
+        BettingStructure::NoLimit => "No Limit",
+        BettingStructure::PotLimit => "Pot Limit",
+        BettingStructure::FixedLimit { .. } => "Limit",
+        BettingStructure::SpreadLimit { .. } => "Spread Limit",
+    };
+    format!("{variant} {betting}")
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time, followed by
+/// the Eastern time in brackets like PokerStars writes after local times.
+fn format_time(time: u64) -> String {
+    format!(
+        "{} UTC [{} ET]",
+        format_date(time),
+        format_date(to_eastern(time))
+    )
+}
+
+fn format_date(time: u64) -> String {
+    let (year, month, day) = civil_from_days((time / 86400) as i64);
+    let seconds = time % 86400;
+    format!(
+        "{year}/{month:02}/{day:02} {:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Year, month and day of a number of days since the Unix epoch. Howard
+/// Hinnant's civil_from_days.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = match shifted_month < 10 {
+        true => shifted_month + 3,
+        false => shifted_month - 9,
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Reads every hand in the text, skipping lines before the first hand.
+///
+/// Players are given their seat position as their id, since the text has none.
+/// Amounts are read as cents when the stakes are in a currency. Times must be
+/// in UTC, or in Eastern time like PokerStars adds in brackets after local times.
+/// Lines that do not affect the hand, like chat, are ignored.
+pub fn read_hands(text: &str) -> Result<Vec<HandHistory>, String> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}').trim()))
+        .collect();
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, line))| line.starts_with("PokerStars "))
+        .map(|(i, _)| i)
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(lines.len());
+            read_hand(&lines[start..end])
+        })
+        .collect()
+}
+
+/// Reads one hand, given its lines with their line numbers.
+fn read_hand(lines: &[(usize, &str)]) -> Result<HandHistory, String> {
+    let (line_number, header) = lines[0];
+    let error = |line: usize, message: &str| format!("Line {line}: {message}");
+    let (history, cents) = read_header(header).map_err(|e| error(line_number, &e))?;
+    let mut reader = HandReader {
+        history,
+        cents,
+        street: None,
+        bets: Vec::new(),
+        stacks: Vec::new(),
+        summary: false,
+        showdown: false,
+    };
+    for &(line_number, line) in &lines[1..] {
+        if line.is_empty() {
+            continue;
+        }
+        reader.read_line(line).map_err(|e| error(line_number, &e))?;
+    }
+    let mut history = reader.history;
+    if history.seats.is_empty() {
+        return Err(error(line_number, "Hand has no seats"));
+    }
+    let max_seat = history.seats.iter().map(|seat| seat.position + 1).max();
+    history.options.num_seats = history.options.num_seats.max(max_seat.unwrap_or(0));
+    history.options.ante = history
+        .posts
+        .iter()
+        .find(|post| post.kind == PostKind::Ante)
+        .map_or(0, |post| post.chips);
+    if history
+        .posts
+        .iter()
+        .any(|post| post.kind == PostKind::Straddle)
+    {
+        history.options.straddle = Straddle::UnderTheGun;
+    }
+    Ok(history)
+}
+
+/// Reads the first line of a hand, returning an empty history and whether amounts are in cents.
+fn read_header(header: &str) -> Result<(HandHistory, bool), String> {
+    let number_start = ["Hand #", "Game #"]
+        .iter()
+        .find_map(|prefix| Some(header.find(prefix)? + prefix.len()))
+        .ok_or("Missing hand number")?;
+    let number: String = header[number_start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    let number = number.parse().map_err(|_| "Invalid hand number")?;
+    if !header.contains("Hold'em") {
+        return Err(String::from("Only Hold'em hands are supported"));
+    }
+    let variant = match header.contains("6+ Hold'em") {
+        true => Variant::ShortDeck,
+        false => Variant::Standard,
+    };
+    let (stakes, small, big, cents) = header
+        .match_indices('(')
+        .find_map(|(i, _)| {
+            let stakes = &header[i + 1..i + header[i..].find(')')?];
+            let blinds = stakes.split_whitespace().next()?;
+            let cents = blinds.starts_with(['$', '€', '£']);
+            let (small, big) = blinds.split_once('/')?;
+            Some((stakes, amount(small, cents)?, amount(big, cents)?, cents))
+        })
+        .ok_or("Missing stakes")?;
+    let (small_blind, big_blind, betting) = if header.contains("No Limit") {
+        (small, big, BettingStructure::NoLimit)
+    } else if header.contains("Pot Limit") {
+        (small, big, BettingStructure::PotLimit)
+    } else if header.contains("Spread Limit") {
+        // Written after the blinds, like `(1/2 spread 2-10)`.
+        let (min_bet, max_bet) = stakes
+            .split_once(" spread ")
+            .and_then(|(_, spread)| spread.split_once('-'))
+            .and_then(|(min, max)| Some((amount(min, cents)?, amount(max, cents)?)))
+            .ok_or("Missing spread")?;
+        let betting = BettingStructure::SpreadLimit { min_bet, max_bet };
+        (small, big, betting)
+    } else if header.contains("Limit") {
+        let betting = BettingStructure::FixedLimit {
+            small_bet: small,
+            big_bet: big,
+            max_raises: 4,
+        };
+        (small / 2, small, betting)
+    } else {
+        return Err(String::from("Unknown betting structure"));
+    };
+    let history = HandHistory {
+        number,
+        seed: None,
+        time: read_header_time(header)?,
+        options: GameOptions {
+            num_seats: 0,
+            min_buyin: 0,
+            max_buyin: u64::MAX,
+            small_blind,
+            big_blind,
+            variant,
+            betting,
+            ante: 0,
+            big_blind_ante: false,
+            straddle: Straddle::Off,
+            rake: Rake::default(),
+        },
+        seats: Vec::new(),
+        button: 0,
+        posts: Vec::new(),
+        hole_cards: Vec::new(),
+        actions: Vec::new(),
+        board: Vec::new(),
+        uncalled: None,
+        showdown: Vec::new(),
+        awards: Vec::new(),
+        rake: 0,
+    };
+    Ok((history, cents))
+}
+
+struct HandReader {
+    history: HandHistory,
+    cents: bool,
+    /// `None` until the hole cards are dealt.
+    street: Option<Street>,
+    /// Each position's bet on the current street.
+    bets: Vec<u64>,
+    /// Chips each position has behind.
+    stacks: Vec<u64>,
+    showdown: bool,
+    summary: bool,
+}
+
+impl HandReader {
+    fn read_line(&mut self, line: &str) -> Result<(), String> {
+        if let Some(section) = line.strip_prefix("*** ") {
+            return self.read_section(section);
+        }
+        if self.summary {
+            if let Some(total) = line.strip_prefix("Total pot ") {
+                if let Some((_, rake)) = total.split_once("| Rake ") {
+                    let rake = rake.split_whitespace().next().unwrap_or("");
+                    self.history.rake = self.amount(rake)?;
+                }
+            }
+            return Ok(());
+        }
+        if let Some(table) = line.strip_prefix("Table '") {
+            return self.read_table(table);
+        }
+        if self.street.is_none() {
+            if let Some(seat) = line.strip_prefix("Seat ") {
+                return self.read_seat(seat);
+            }
+        }
+        if let Some(rest) = line.strip_prefix("Dealt to ") {
+            let (position, rest) = self.player_prefix(rest, " [")?;
+            let cards = rest.split(']').next().unwrap_or("");
+            self.set_hole_cards(position, parse_cards(cards)?);
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix("Uncalled bet (") {
+            let (chips, name) = rest
+                .split_once(") returned to ")
+                .ok_or("Invalid uncalled bet")?;
+            let chips = self.amount(chips)?;
+            let position = self.position(name)?;
+            self.bets[position] -= chips.min(self.bets[position]);
+            self.stacks[position] += chips;
+            self.history.uncalled = Some((position, chips));
+            return Ok(());
+        }
+        if let Ok((position, rest)) = self.player_prefix(line, " collected ") {
+            let (chips, pot) = rest.split_once(" from ").ok_or("Invalid collection")?;
+            let pot = match pot {
+                "pot" | "main pot" => 0,
+                "side pot" => 1,
+                _ => match pot.strip_prefix("side pot-") {
+                    Some(n) => n.parse().map_err(|_| format!("Invalid pot '{pot}'"))?,
+                    None => return Err(format!("Invalid pot '{pot}'")),
+                },
+            };
+            let chips = self.amount(chips)?;
+            self.history.awards.push(Award {
+                position,
+                pot,
+                chips,
+            });
+            return Ok(());
+        }
+        match self.player_prefix(line, ": ") {
+            Ok((position, rest)) => self.read_player_line(position, rest),
+            // Anything else, like chat or players joining, does not change the hand.
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn read_section(&mut self, section: &str) -> Result<(), String> {
+        let (name, cards) = section.split_once(" ***").unwrap_or((section, ""));
+        let street = match name {
+            "HOLE CARDS" => Street::Preflop,
+            "FLOP" => Street::Flop,
+            "TURN" => Street::Turn,
+            "RIVER" => Street::River,
+            "SHOW DOWN" => {
+                self.showdown = true;
+                return Ok(());
+            }
+            "SUMMARY" => {
+                self.summary = true;
+                return Ok(());
+            }
+            _ => return Err(format!("Unknown section '{name}'")),
+        };
+        if street != Street::Preflop {
+            let new_cards = cards.rsplit('[').next().unwrap_or("");
+            let new_cards = parse_cards(new_cards.trim_end_matches(']'))?;
+            self.history.board.extend(new_cards);
+            self.bets.fill(0);
+        }
+        self.street = Some(street);
+        Ok(())
+    }
+
+    fn read_table(&mut self, table: &str) -> Result<(), String> {
+        let (_, rest) = table.rsplit_once('\'').ok_or("Invalid table")?;
+        if let Some(max) = rest.split_whitespace().find_map(|w| w.strip_suffix("-max")) {
+            self.history.options.num_seats = max.parse().map_err(|_| "Invalid table size")?;
+        }
+        let button = rest
+            .split_once("Seat #")
+            .and_then(|(_, seat)| seat.split_whitespace().next())
+            .ok_or("Missing button")?;
+        let button: usize = button.parse().map_err(|_| "Invalid button")?;
+        self.history.button = button.checked_sub(1).ok_or("Invalid button")?;
+        Ok(())
+    }
+
+    fn read_seat(&mut self, seat: &str) -> Result<(), String> {
+        let (number, rest) = seat.split_once(": ").ok_or("Invalid seat")?;
+        let number: usize = number.parse().map_err(|_| "Invalid seat number")?;
+        let position = number.checked_sub(1).ok_or("Invalid seat number")?;
+        let chips_end = rest.rfind(" in chips").ok_or("Missing chips")?;
+        let chips_start = rest[..chips_end].rfind(" (").ok_or("Missing chips")?;
+        let chips = self.amount(&rest[chips_start + 2..chips_end])?;
+        let after = &rest[chips_end..];
+        self.history.seats.push(Seat {
+            position,
+            id: position as u64,
+            name: String::from(&rest[..chips_start]),
+            chips,
+            dealt_in: !after.contains("sitting out") && !after.contains("out of hand"),
+        });
+        if self.bets.len() <= position {
+            self.bets.resize(position + 1, 0);
+            self.stacks.resize(position + 1, 0);
+        }
+        self.stacks[position] = chips;
+        Ok(())
+    }
+
+    fn read_player_line(&mut self, position: usize, line: &str) -> Result<(), String> {
+        if let Some(post) = line.strip_prefix("posts ") {
+            return self.read_post(position, post);
+        }
+        if let Some(shown) = line.strip_prefix("shows [") {
+            let cards = parse_cards(shown.split(']').next().unwrap_or(""))?;
+            self.set_hole_cards(position, cards.clone());
+            if self.showdown {
+                let cards: Result<[Card; 7], _> =
+                    [&cards[..], &self.history.board[..]].concat().try_into();
+                if let Ok(cards) = cards {
+                    let hand = self.history.options.variant.best_hand(cards);
+                    self.history.showdown.push((position, hand));
+                }
+            }
+            return Ok(());
+        }
+        let Some(street) = self.street else {
+            return Ok(());
+        };
+        let (line, all_in) = match line.strip_suffix(" and is all-in") {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let mut words = line.split_whitespace();
+        let (action, chips) = match (words.next(), words.next(), words.next(), words.next()) {
+            (Some("folds"), ..) => (Action::Fold, 0),
+            (Some("checks"), ..) => (Action::Check, 0),
+            (Some("calls"), Some(chips), ..) => (Action::Call, self.amount(chips)?),
+            (Some("bets"), Some(chips), ..) => {
+                let chips = self.amount(chips)?;
+                (Action::Bet(self.bets[position] + chips), chips)
+            }
+            (Some("raises"), Some(_), Some("to"), Some(to)) => {
+                let to = self.amount(to)?;
+                let chips = to
+                    .checked_sub(self.bets[position])
+                    .ok_or("Raise is smaller than the player's bet")?;
+                (Action::Raise(to), chips)
+            }
+            // Other lines, like mucking or sitting out, do not change the hand.
+            _ => return Ok(()),
+        };
+        if all_in && chips != self.stacks[position] {
+            return Err(format!(
+                "All in for {chips} with {} behind",
+                self.stacks[position]
+            ));
+        }
+        self.spend(position, chips)?;
+        self.bets[position] += chips;
+        self.history.actions.push(ActionRecord {
+            street,
+            position,
+            action,
+            chips,
+            all_in,
+        });
+        Ok(())
+    }
+
+    fn read_post(&mut self, position: usize, post: &str) -> Result<(), String> {
+        let (kind, chips) = post.rsplit_once(' ').ok_or("Invalid post")?;
+        let chips = self.amount(chips)?;
+        let big_blind = self.history.options.big_blind;
+        let posts: &[(PostKind, u64)] = match kind {
+            "the ante" => &[(PostKind::Ante, chips)],
+            "small blind" => &[(PostKind::SmallBlind, chips)],
+            "big blind" => &[(PostKind::BigBlind, chips)],
+            "straddle" => &[(PostKind::Straddle, chips)],
+            "small & big blinds" => &[
+                (PostKind::BigBlind, chips.min(big_blind)),
+                (PostKind::Dead, chips.saturating_sub(big_blind)),
+            ],
+            _ => return Err(format!("Unknown post '{kind}'")),
+        };
+        for &(kind, chips) in posts {
+            self.spend(position, chips)?;
+            if !matches!(kind, PostKind::Ante | PostKind::Dead) {
+                self.bets[position] += chips;
+            }
+            self.history.posts.push(Post {
+                position,
+                kind,
+                chips,
+            });
+        }
+        Ok(())
+    }
+
+    /// Takes chips from the position's stack.
+    fn spend(&mut self, position: usize, chips: u64) -> Result<(), String> {
+        let stack = &mut self.stacks[position];
+        *stack = stack
+            .checked_sub(chips)
+            .ok_or_else(|| format!("Not enough chips for {chips}, {stack} behind"))?;
+        Ok(())
+    }
+
+    fn set_hole_cards(&mut self, position: usize, cards: Vec<Card>) {
+        let hole_cards = &mut self.history.hole_cards;
+        match hole_cards.iter_mut().find(|(p, _)| *p == position) {
+            Some((_, known)) => *known = cards,
+            None => hole_cards.push((position, cards)),
+        }
+    }
+
+    /// Splits a line that starts with a seated player's name followed by the separator.
+    fn player_prefix<'a>(
+        &self,
+        line: &'a str,
+        separator: &str,
+    ) -> Result<(usize, &'a str), String> {
+        self.history
+            .seats
+            .iter()
+            .filter(|seat| {
+                line.strip_prefix(seat.name.as_str())
+                    .is_some_and(|rest| rest.starts_with(separator))
+            })
+            .max_by_key(|seat| seat.name.len())
+            .map(|seat| (seat.position, &line[seat.name.len() + separator.len()..]))
+            .ok_or_else(|| format!("Unknown player in '{line}'"))
+    }
+
+    fn position(&self, name: &str) -> Result<usize, String> {
+        self.history
+            .seats
+            .iter()
+            .find(|seat| seat.name == name)
+            .map(|seat| seat.position)
+            .ok_or_else(|| format!("Unknown player '{name}'"))
+    }
+
+    fn amount(&self, s: &str) -> Result<u64, String> {
+        amount(s, self.cents).ok_or_else(|| format!("Invalid amount '{s}'"))
+    }
+}
+
+/// Reads an amount like `200`, `$1.50` or `1,000`, in cents if `cents` is set.
+fn amount(s: &str, cents: bool) -> Option<u64> {
+    let s: String = s
+        .trim_start_matches(['$', '€', '£'])
+        .chars()
+        .filter(|&c| c != ',')
+        .collect();
+    let (whole, fraction) = s.split_once('.').unwrap_or((&s, ""));
+    if !cents {
+        return match fraction.trim_end_matches('0') {
+            "" => whole.parse().ok(),
+            _ => None,
+        };
+    }
+    if fraction.len() > 2 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = format!("{fraction:0<2}").parse::<u64>().ok()?;
+    Some(whole.parse::<u64>().ok()? * 100 + fraction)
+}
+
+/// Reads the time after the stakes into seconds since the Unix epoch, or 0 if
+/// there is none.
+///
+/// Only UTC and Eastern times are supported, so for a local time like
+/// `2024/01/31 19:05:00 CET [2024/01/31 13:05:00 ET]` the Eastern time is used.
+fn read_header_time(header: &str) -> Result<u64, String> {
+    let mut error = None;
+    for time in header.split(" - ").skip(1).flat_map(|s| s.split('[')) {
+        let mut words = time.trim().trim_end_matches(']').split_whitespace();
+        let (Some(date), Some(clock)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Some(local) = read_time(date, clock) else {
+            continue;
+        };
+        match words.next() {
+            Some("UTC" | "GMT") => return Ok(local),
+            Some("ET") => return Ok(local + eastern_offset(local)),
+            Some(zone) => error = Some(format!("Unsupported time zone '{zone}'")),
+            None => error = Some(String::from("Missing time zone")),
+        }
+    }
+    match error {
+        Some(error) => Err(error),
+        None => Ok(0),
+    }
+}
+
+/// Reads a date and time like `2024/01/31 18:05:00` into seconds since the Unix epoch.
+fn read_time(date: &str, time: &str) -> Option<u64> {
+    let date: Vec<i64> = date
+        .split('/')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    let time: Vec<u64> = time
+        .split(':')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    let (&[year, month, day], &[hours, minutes, seconds]) = (&date[..], &time[..]) else {
+        return None;
+    };
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Days since the Unix epoch. Howard Hinnant's days_from_civil.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+const EST: u64 = 5 * 3600;
+const EDT: u64 = 4 * 3600;
+
+/// Seconds to add to a US Eastern time to get UTC.
+fn eastern_offset(local: u64) -> u64 {
+    let (start, end) = daylight_saving(local);
+    match (start..end).contains(&local) {
+        true => EDT,
+        false => EST,
+    }
+}
+
+/// US Eastern time of a UTC time, in seconds since the Unix epoch.
+fn to_eastern(time: u64) -> u64 {
+    let (start, end) = daylight_saving(time);
+    match (start + EST..end + EDT).contains(&time) {
+        true => time.saturating_sub(EDT),
+        false => time.saturating_sub(EST),
+    }
+}
+
+/// Eastern times that daylight saving time starts and ends in the year of the
+/// given time: 2am on the second Sunday in March and the first Sunday in November.
+fn daylight_saving(time: u64) -> (u64, u64) {
+    let (year, _, _) = civil_from_days((time / 86400) as i64);
+    // 1970/01/01 was a Thursday.
+    let sunday = |month: i64, n: i64| {
+        let first = days_from_civil(year, month, 1);
+        let first_sunday = first + (3 - first).rem_euclid(7);
+        ((first_sunday + 7 * (n - 1)) * 86400 + 2 * 3600) as u64
+    };
+    (sunday(3, 2), sunday(11, 1))
+}
@@ -0,0 +1,228 @@
+use std::fmt::Debug;
+
+use super::{
+    history::{HandHistory, PostKind},
+    ledger::Transaction,
+    Game, Rake,
+};
+use crate::{
+    card::Card,
+    dealer::Dealer,
+    player::{Player, Status},
+};
+
+/// Plays a recorded hand back one action at a time.
+///
+/// The hand is dealt from the recorded seed, or when there is none, from a
+/// deck stacked with the recorded cards. Blinds, including missed blinds, are
+/// posted by the players who posted them in the recorded hand. Hands recorded
+/// without rake rules, like imported hands, take the recorded rake.
+#[derive(Debug)]
+pub struct Replay {
+    history: HandHistory,
+    game: Game,
+    /// Number of recorded actions taken so far.
+    index: usize,
+}
+
+impl Replay {
+    pub fn new(history: HandHistory) -> Result<Self, String> {
+        let game = start(&history)?;
+        Ok(Self {
+            history,
+            game,
+            index: 0,
+        })
+    }
+
+    /// State of the game after the actions taken so far.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn history(&self) -> &HandHistory {
+        &self.history
+    }
+
+    /// Number of actions taken so far.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Number of actions in the hand.
+    pub fn len(&self) -> usize {
+        self.history.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.history.actions.is_empty()
+    }
+
+    /// Takes the next recorded action, returning `false` if there are none left.
+    pub fn step_forward(&mut self) -> Result<bool, String> {
+        let Some(record) = self.history.actions.get(self.index) else {
+            return Ok(false);
+        };
+        if self.game.turn() != record.position {
+            return Err(format!(
+                "Action {} is by seat {}, but it is seat {}'s turn",
+                self.index,
+                record.position,
+                self.game.turn()
+            ));
+        }
+        self.game
+            .act(record.action)
+            .map_err(|error| format!("Action {}: {error}", self.index))?;
+        self.index += 1;
+        Ok(true)
+    }
+
+    /// Undoes the last action, returning `false` if there are none to undo.
+    pub fn step_back(&mut self) -> Result<bool, String> {
+        if self.index == 0 {
+            return Ok(false);
+        }
+        self.seek(self.index - 1)?;
+        Ok(true)
+    }
+
+    /// Goes to the state after the given number of actions.
+    ///
+    /// Going backward replays the hand from the start.
+    pub fn seek(&mut self, index: usize) -> Result<(), String> {
+        if index > self.len() {
+            return Err(format!("The hand only has {} actions", self.len()));
+        }
+        if index < self.index {
+            self.game = start(&self.history)?;
+            self.index = 0;
+        }
+        while self.index < index {
+            self.step_forward()?;
+        }
+        Ok(())
+    }
+
+    /// Replays the rest of the hand and checks it ends the way it was recorded.
+    pub fn verify(&mut self) -> Result<(), String> {
+        self.seek(self.len())?;
+        let replayed = self
+            .game
+            .histories()
+            .last()
+            .ok_or("The hand did not finish")?;
+        let recorded = &self.history;
+        let differ = |what: &str, replayed: &dyn Debug, recorded: &dyn Debug| {
+            format!("Replayed {what} {replayed:?} differ from recorded {recorded:?}")
+        };
+        if replayed.posts != recorded.posts {
+            return Err(differ("posts", &replayed.posts, &recorded.posts));
+        }
+        if replayed.board != recorded.board {
+            return Err(differ("board", &replayed.board, &recorded.board));
+        }
+        if replayed.uncalled != recorded.uncalled {
+            return Err(differ(
+                "uncalled bet",
+                &replayed.uncalled,
+                &recorded.uncalled,
+            ));
+        }
+        if replayed.showdown != recorded.showdown {
+            return Err(differ("showdown", &replayed.showdown, &recorded.showdown));
+        }
+        if replayed.awards != recorded.awards || replayed.rake != recorded.rake {
+            return Err(differ(
+                "awards",
+                &(&replayed.awards, replayed.rake),
+                &(&recorded.awards, recorded.rake),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Seats the recorded players and deals the hand.
+fn start(history: &HandHistory) -> Result<Game, String> {
+    let mut game = Game::new(history.options.clone())?;
+    game.hands = history.number.saturating_sub(1);
+    game.button = history.button;
+    let find_post = |kind| {
+        history
+            .posts
+            .iter()
+            .find(|post| post.kind == kind)
+            .map(|post| post.position)
+    };
+    let big_blind = find_post(PostKind::BigBlind);
+    for seat in &history.seats {
+        let mut player = Player::new(seat.id, &seat.name, seat.chips);
+        if !seat.dealt_in {
+            player.status = Status::SittingOut;
+        }
+        for post in history.posts.iter().filter(|p| p.position == seat.position) {
+            match post.kind {
+                PostKind::Dead => player.missed_small_blind = true,
+                PostKind::BigBlind if Some(seat.position) != big_blind => {
+                    player.missed_big_blind = true
+                }
+                _ => (),
+            }
+        }
+        match game.seats.get_mut(seat.position) {
+            Some(empty @ None) => *empty = Some(player),
+            _ => return Err(format!("Cannot seat a player in seat {}", seat.position)),
+        }
+        game.num_players += 1;
+        game.ledger
+            .record(game.hands, seat.id, Transaction::BuyIn(seat.chips));
+    }
+    let big_blind = big_blind.ok_or("The hand has no big blind")?;
+    game.replayed_blinds = Some((find_post(PostKind::SmallBlind), big_blind));
+    // Imported hands record the rake taken, but not how it was worked out.
+    if history.options.rake == Rake::default() {
+        game.replayed_rake = Some(history.rake);
+    }
+    game.stack_deck(deck(history))?;
+    game.start_hand()?;
+    Ok(game)
+}
+
+/// The cards in the order they were dealt.
+fn deck(history: &HandHistory) -> Vec<Card> {
+    let variant = history.options.variant;
+    if let Some(seed) = history.seed {
+        let mut dealer = Dealer::with_seed(variant.deck(), seed);
+        return dealer.deal(variant.deck().len());
+    }
+    // Unknown cards are filled in from the rest of the deck.
+    let known: Vec<Card> = history
+        .hole_cards
+        .iter()
+        .flat_map(|(_, cards)| cards.iter().copied())
+        .chain(history.board.iter().copied())
+        .collect();
+    let mut rest: Vec<Card> = variant
+        .deck()
+        .into_iter()
+        .filter(|card| !known.contains(card))
+        .collect();
+    let mut deck = Vec::new();
+    let mut dealt_in: Vec<usize> = history
+        .seats
+        .iter()
+        .filter(|seat| seat.dealt_in)
+        .map(|seat| seat.position)
+        .collect();
+    dealt_in.sort_unstable();
+    for position in dealt_in {
+        match history.hole_cards.iter().find(|(p, _)| *p == position) {
+            Some((_, cards)) => deck.extend(cards),
+            None => deck.extend(rest.drain(..2.min(rest.len()))),
+        }
+    }
+    deck.extend(&history.board);
+    deck.extend(rest);
+    deck
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Game;
+
+/// Version of the snapshot format. Raise it whenever the serialized form of
+/// `Game` changes, and add a step to `migrate` that upgrades the old form.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    version: u32,
+    game: &'a Game,
+}
+
+#[derive(Deserialize)]
+struct StoredSnapshot {
+    version: u32,
+    game: Value,
+}
+
+impl Game {
+    /// Saves the whole state of the game as JSON, including a hand in progress
+    /// and the order of the cards left in the deck.
+    pub fn snapshot(&self) -> Result<String, String> {
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            game: self,
+        };
+        serde_json::to_string(&snapshot).map_err(|error| error.to_string())
+    }
+
+    pub fn snapshot_bytes(&self) -> Result<Vec<u8>, String> {
+        self.snapshot().map(String::into_bytes)
+    }
+
+    /// Restores a game saved by `snapshot`, upgrading snapshots from older versions.
+    pub fn restore(snapshot: &str) -> Result<Game, String> {
+        let stored: StoredSnapshot =
+            serde_json::from_str(snapshot).map_err(|error| format!("Invalid snapshot: {error}"))?;
+        let game = migrate(stored.version, stored.game)?;
+        let game: Game =
+            serde_json::from_value(game).map_err(|error| format!("Invalid snapshot: {error}"))?;
+        game.check_chips()?;
+        Ok(game)
+    }
+
+    pub fn restore_bytes(snapshot: &[u8]) -> Result<Game, String> {
+        let snapshot =
+            std::str::from_utf8(snapshot).map_err(|error| format!("Invalid snapshot: {error}"))?;
+        Game::restore(snapshot)
+    }
+}
+
+/// Upgrades a serialized game from the given version to the current one.
+fn migrate(version: u32, game: Value) -> Result<Value, String> {
+    match version {
+        SNAPSHOT_VERSION => Ok(game),
+        v if v > SNAPSHOT_VERSION => Err(format!(
+            "Snapshot version {v} is newer than the supported version {SNAPSHOT_VERSION}"
+        )),
+        v => Err(format!("Snapshot version {v} is no longer supported")),
+    }
+}
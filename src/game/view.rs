@@ -0,0 +1,117 @@
+use super::{event::GameEvent, Game, Street};
+use crate::{
+    card::Card,
+    player::{Player, Status},
+};
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state of a game as one seat or a spectator can see it.
+///
+/// Other players' hole cards are hidden until they are shown at showdown,
+/// and the cards left in the deck are never included.
+pub struct GameView {
+    /// Seat the view is for, or `None` for a spectator.
+    pub seat: Option<usize>,
+    pub seats: Vec<Option<PlayerView>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
+    pub board: Vec<Card>,
+    pub button: usize,
+    /// Seat whose turn it is, or `None` between hands.
+    pub turn: Option<usize>,
+    pub street: Option<Street>,
+    /// Number of hands started.
+    pub hand: u64,
+    /// Chips in the pot, not counting bets on the current street.
+    pub pot: u64,
+    pub current_bet: u64,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView {
+    pub id: u64,
+    pub name: String,
+    pub chips: u64,
+    pub bet: u64,
+    pub status: Status,
+    /// The player's hole cards, or `None` if they hold cards the viewer cannot see.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::card::as_string::option_cards")
+    )]
+    pub cards: Option<Vec<Card>>,
+}
+
+impl Game {
+    /// The game as the player in this seat sees it.
+    pub fn view_for(&self, seat: usize) -> GameView {
+        self.view(Some(seat))
+    }
+
+    /// The game as someone watching the table sees it.
+    pub fn spectator_view(&self) -> GameView {
+        self.view(None)
+    }
+
+    fn view(&self, seat: Option<usize>) -> GameView {
+        let seats = self
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(position, player)| {
+                let player = player.as_ref()?;
+                let visible =
+                    player.cards.is_empty() || Some(position) == seat || self.shown_down(position);
+                Some(player_view(player, visible))
+            })
+            .collect();
+        GameView {
+            seat,
+            seats,
+            board: self.board.clone(),
+            button: self.button,
+            turn: self.street.map(|_| self.turn),
+            street: self.street,
+            hand: self.hands,
+            pot: self.pot,
+            current_bet: self.current_bet(),
+        }
+    }
+
+    /// Whether the player showed their cards at the end of the last hand.
+    fn shown_down(&self, position: usize) -> bool {
+        self.street.is_none()
+            && self.histories.last().is_some_and(|history| {
+                history.number == self.hands && history.showdown.iter().any(|&(p, _)| p == position)
+            })
+    }
+}
+
+impl GameEvent {
+    /// The event as the player in this seat, or a spectator when `None`, sees it.
+    ///
+    /// Cards dealt to other players are hidden. Cards shown at showdown are not.
+    pub fn visible_to(self, seat: Option<usize>) -> GameEvent {
+        match self {
+            GameEvent::CardsDealt { position, .. } if Some(position) != seat => {
+                GameEvent::CardsDealt {
+                    position,
+                    cards: None,
+                }
+            }
+            event => event,
+        }
+    }
+}
+
+fn player_view(player: &Player, visible: bool) -> PlayerView {
+    PlayerView {
+        id: player.id,
+        name: player.name.clone(),
+        chips: player.chips,
+        bet: player.bet,
+        status: player.status,
+        cards: visible.then(|| player.cards.clone()),
+    }
+}
@@ -1,9 +1,13 @@
-use self::Hand::*;
-use crate::card::Rank;
-use std::cmp::Ordering::{self, *};
+use self::{Hand::*, Variant::*};
+use crate::card::{deck_36, deck_52, Card, Rank};
+use std::{
+    cmp::Ordering::{self, *},
+    fmt,
+};
 
 pub mod five_card_hand;
 pub mod seven_card_hand;
+pub mod wild_hand;
 
 pub type HighCardRanks = [Rank; 5];
 pub type OnePairRanks = [Rank; 4];
@@ -13,7 +17,8 @@ pub type FlushRanks = [Rank; 5];
 pub type FullHouseRanks = [Rank; 2];
 pub type FourOfAKindRanks = [Rank; 2];
 
-#[derive(Eq, Debug)]
+#[derive(Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Hands with relevant ranks.
 ///
 /// Ranks are compared from left to right.
@@ -36,17 +41,19 @@ pub enum Hand {
     FourOfAKind(FourOfAKindRanks),
     StraightFlush(Rank),
     RoyalFlush,
+    /// Only possible with wild cards.
+    FiveOfAKind(Rank),
 }
 
 impl PartialEq for Hand {
     fn eq(&self, other: &Self) -> bool {
-        self.cmp(&other) == Equal
+        self.cmp(other) == Equal
     }
 }
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
@@ -81,6 +88,37 @@ impl Ord for Hand {
             (StraightFlush(_), _) => Less,
             (_, StraightFlush(_)) => Greater,
             (RoyalFlush, RoyalFlush) => Equal,
+            (RoyalFlush, _) => Less,
+            (_, RoyalFlush) => Greater,
+            (FiveOfAKind(a), FiveOfAKind(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// Describes the hand the way hand histories do, like "two pair, Kings and Sevens".
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HighCard([high, ..]) => write!(f, "high card {}", high.name()),
+            OnePair([pair, ..]) => write!(f, "a pair of {}", pair.plural()),
+            TwoPair([high, low, _]) => {
+                write!(f, "two pair, {} and {}", high.plural(), low.plural())
+            }
+            ThreeOfAKind([trips, ..]) => write!(f, "three of a kind, {}", trips.plural()),
+            Straight(high) => write!(f, "a straight, {} high", high.name()),
+            Flush([high, ..]) => write!(f, "a flush, {} high", high.name()),
+            FullHouse([trips, pair]) => {
+                write!(
+                    f,
+                    "a full house, {} full of {}",
+                    trips.plural(),
+                    pair.plural()
+                )
+            }
+            FourOfAKind([quads, _]) => write!(f, "four of a kind, {}", quads.plural()),
+            StraightFlush(high) => write!(f, "a straight flush, {} high", high.name()),
+            RoyalFlush => write!(f, "a Royal Flush"),
+            FiveOfAKind(rank) => write!(f, "five of a kind, {}", rank.plural()),
         }
     }
 }
@@ -96,3 +134,41 @@ fn cmp_ranks<const N: usize>(a: &[Rank; N], b: &[Rank; N]) -> Ordering {
     }
     Equal
 }
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Rule sets that change which cards are in the deck and how hands rank.
+pub enum Variant {
+    /// Texas hold'em with a 52 card deck.
+    #[default]
+    Standard,
+    /// Short deck (6+) hold'em with a 36 card deck.
+    ///
+    /// Flushes beat full houses, and A-6-7-8-9 is the lowest straight.
+    ShortDeck,
+}
+
+impl Variant {
+    pub fn deck(self) -> Vec<Card> {
+        match self {
+            Standard => deck_52(),
+            ShortDeck => deck_36(),
+        }
+    }
+
+    pub fn best_hand(self, cards: [Card; 7]) -> Hand {
+        match self {
+            Standard => seven_card_hand::best_hand(cards),
+            ShortDeck => seven_card_hand::best_short_deck_hand(cards),
+        }
+    }
+
+    /// Compares hands using the ranking of this variant.
+    pub fn cmp_hands(self, a: &Hand, b: &Hand) -> Ordering {
+        match (self, a, b) {
+            (ShortDeck, Flush(_), FullHouse(_)) => Greater,
+            (ShortDeck, FullHouse(_), Flush(_)) => Less,
+            _ => a.cmp(b),
+        }
+    }
+}
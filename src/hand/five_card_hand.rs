@@ -10,7 +10,7 @@ use crate::card::{
 use std::cmp::Reverse;
 
 pub fn best_hand(cards: [Card; 5]) -> Hand {
-    let mut cards = cards.clone();
+    let mut cards = cards;
     cards.sort_by_key(|&(r, _)| Reverse(r));
     if is_royal_flush(cards) {
         return RoyalFlush;
@@ -47,16 +47,15 @@ fn is_royal_flush(cards: [Card; 5]) -> bool {
 }
 
 fn is_royal(cards: [Card; 5]) -> bool {
-    match cards {
-        [(Ace, _), (King, _), (Queen, _), (Jack, _), (Ten, _)] => true,
-        _ => false,
-    }
+    matches!(
+        cards,
+        [(Ace, _), (King, _), (Queen, _), (Jack, _), (Ten, _)]
+    )
 }
 
 fn is_straight_flush(cards: [Card; 5]) -> Option<Rank> {
-    if is_straight(cards).is_some() && is_flush(cards).is_some() {
-        let (r, _) = cards[0];
-        return Some(r);
+    if is_flush(cards).is_some() {
+        return is_straight(cards);
     };
     None
 }
@@ -110,6 +109,9 @@ fn is_three_of_a_kind(cards: [Card; 5]) -> Option<ThreeOfAKindRanks> {
     if a == b && a == c {
         return Some([a, d, e]);
     }
+    if b == c && b == d {
+        return Some([b, a, e]);
+    }
     if c == d && c == e {
         return Some([c, a, b]);
     }
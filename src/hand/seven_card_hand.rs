@@ -7,15 +7,23 @@ use crate::card::{
     Card,
     Rank::{self, *},
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::cmp::Reverse;
 
+/// The four lowest ranks of the lowest straight, completed by an ace.
+type Wheel = [Rank; 4];
+
+const WHEEL: Wheel = [Two, Three, Four, Five];
+const SHORT_DECK_WHEEL: Wheel = [Six, Seven, Eight, Nine];
+
 pub fn best_hand(cards: [Card; 7]) -> Hand {
-    let mut cards = cards.clone();
+    let mut cards = cards;
     cards.sort_by_key(|&(r, _)| Reverse(r));
     if is_royal_flush(cards) {
         return RoyalFlush;
     };
-    if let Some(ranks) = is_straight_flush(cards) {
+    if let Some(ranks) = is_straight_flush(cards, WHEEL) {
         return StraightFlush(ranks);
     };
     if let Some(ranks) = is_four_of_a_kind(cards) {
@@ -27,9 +35,53 @@ pub fn best_hand(cards: [Card; 7]) -> Hand {
     if let Some(ranks) = is_flush(cards) {
         return Flush(ranks);
     };
-    if let Some(ranks) = is_straight(cards) {
+    if let Some(ranks) = is_straight(cards, WHEEL) {
         return Straight(ranks);
     };
+    best_hand_below_straight(cards)
+}
+
+/// Best hand for short deck (6+) hold'em.
+///
+/// Expects cards from a 36 card deck. Flushes are checked before full houses,
+/// and A-6-7-8-9 is the lowest straight.
+pub fn best_short_deck_hand(cards: [Card; 7]) -> Hand {
+    let mut cards = cards;
+    cards.sort_by_key(|&(r, _)| Reverse(r));
+    if is_royal_flush(cards) {
+        return RoyalFlush;
+    };
+    if let Some(ranks) = is_straight_flush(cards, SHORT_DECK_WHEEL) {
+        return StraightFlush(ranks);
+    };
+    if let Some(ranks) = is_four_of_a_kind(cards) {
+        return FourOfAKind(ranks);
+    };
+    if let Some(ranks) = is_flush(cards) {
+        return Flush(ranks);
+    };
+    if let Some(ranks) = is_full_house(cards) {
+        return FullHouse(ranks);
+    };
+    if let Some(ranks) = is_straight(cards, SHORT_DECK_WHEEL) {
+        return Straight(ranks);
+    };
+    best_hand_below_straight(cards)
+}
+
+/// Best hand for each set of cards.
+pub fn best_hands(hands: &[[Card; 7]]) -> Vec<Hand> {
+    hands.iter().map(|&cards| best_hand(cards)).collect()
+}
+
+/// Best hand for each set of cards, evaluated across all cores.
+#[cfg(feature = "parallel")]
+pub fn par_best_hands(hands: &[[Card; 7]]) -> Vec<Hand> {
+    hands.par_iter().map(|&cards| best_hand(cards)).collect()
+}
+
+/// Cards must be sorted from highest to lowest rank.
+fn best_hand_below_straight(cards: [Card; 7]) -> Hand {
     if let Some(ranks) = is_three_of_a_kind(cards) {
         return ThreeOfAKind(ranks);
     }
@@ -50,8 +102,8 @@ fn is_royal_flush(cards: [Card; 7]) -> bool {
             continue;
         }
         let (mut king, mut queen, mut jack, mut ten) = (false, false, false, false);
-        for j in (i + 1)..7 {
-            match cards[j] {
+        for &card in &cards[(i + 1)..] {
+            match card {
                 (King, sb) if sa == sb => king = true,
                 (Queen, sb) if sa == sb => queen = true,
                 (Jack, sb) if sa == sb => jack = true,
@@ -66,13 +118,12 @@ fn is_royal_flush(cards: [Card; 7]) -> bool {
     false
 }
 
-fn is_straight_flush(cards: [Card; 7]) -> Option<Rank> {
+fn is_straight_flush(cards: [Card; 7], wheel: Wheel) -> Option<Rank> {
     for i in 0..=2 {
         let (ra, sa) = cards[i];
         let ua = ra as u8;
         let mut count = 1;
-        for j in (i + 1)..=6 {
-            let (rb, sb) = cards[j];
+        for &(rb, sb) in &cards[(i + 1)..] {
             let ub = rb as u8;
             if sa == sb && ua == (ub + count) {
                 count += 1;
@@ -82,26 +133,22 @@ fn is_straight_flush(cards: [Card; 7]) -> Option<Rank> {
             }
         }
     }
-    is_wheel_flush(cards)
+    is_wheel_flush(cards, wheel)
 }
 
-fn is_wheel_flush(cards: [Card; 7]) -> Option<Rank> {
+fn is_wheel_flush(cards: [Card; 7], wheel: Wheel) -> Option<Rank> {
     for i in 0..=2 {
         let (ra, sa) = cards[i];
         if ra != Ace {
             continue;
         }
-        let (mut two, mut three, mut four, mut five) = (false, false, false, false);
-        for j in (i + 1)..7 {
-            match cards[j] {
-                (Two, sb) if sa == sb => two = true,
-                (Three, sb) if sa == sb => three = true,
-                (Four, sb) if sa == sb => four = true,
-                (Five, sb) if sa == sb => five = true,
-                _ => (),
+        let mut found = [false; 4];
+        for &(rb, sb) in &cards[(i + 1)..] {
+            if let Some(k) = wheel.iter().position(|&r| r == rb) {
+                found[k] |= sa == sb;
             }
-            if two & three & four & five {
-                return Some(Five);
+            if found == [true; 4] {
+                return Some(wheel[3]);
             }
         }
     }
@@ -132,8 +179,7 @@ fn is_flush(cards: [Card; 7]) -> Option<FlushRanks> {
     for i in 0..=2 {
         let (ra, sa) = cards[i];
         let mut ranks = vec![ra];
-        for j in (i + 1)..=6 {
-            let (rb, sb) = cards[j];
+        for &(rb, sb) in &cards[(i + 1)..] {
             if sa == sb {
                 ranks.push(rb)
             }
@@ -160,13 +206,12 @@ fn is_four_of_a_kind(cards: [Card; 7]) -> Option<FourOfAKindRanks> {
     None
 }
 
-fn is_straight(cards: [Card; 7]) -> Option<Rank> {
+fn is_straight(cards: [Card; 7], wheel: Wheel) -> Option<Rank> {
     for i in 0..=2 {
         let (ra, _) = cards[i];
         let ua = ra as u8;
         let mut count = 1;
-        for j in (i + 1)..=6 {
-            let (rb, _) = cards[j];
+        for &(rb, _) in &cards[(i + 1)..] {
             let ub = rb as u8;
             if ua == (ub + count) {
                 count += 1;
@@ -176,22 +221,19 @@ fn is_straight(cards: [Card; 7]) -> Option<Rank> {
             }
         }
     }
-    is_wheel(cards)
+    is_wheel(cards, wheel)
 }
 
-fn is_wheel(cards: [Card; 7]) -> Option<Rank> {
-    let (mut ace, mut two, mut three, mut four, mut five) = (false, false, false, false, false);
+fn is_wheel(cards: [Card; 7], wheel: Wheel) -> Option<Rank> {
+    let mut ace = false;
+    let mut found = [false; 4];
     for (r, _) in cards {
-        match r {
-            Ace => ace = true,
-            Two => two = true,
-            Three => three = true,
-            Four => four = true,
-            Five => five = true,
-            _ => (),
-        }
-        if ace & two & three & four & five {
-            return Some(Five);
+        ace |= r == Ace;
+        if let Some(k) = wheel.iter().position(|&w| w == r) {
+            found[k] = true;
+        }
+        if ace && found == [true; 4] {
+            return Some(wheel[3]);
         }
     }
     None
@@ -0,0 +1,224 @@
+use super::{
+    cmp_ranks, FlushRanks,
+    Hand::{self, *},
+};
+use crate::card::{
+    Card, PlayingCard,
+    Rank::{self, *},
+    RANKS, SUITS,
+};
+use std::cmp::Reverse;
+
+/// Best five card hand that can be made from any number of cards, where jokers
+/// and cards of the wild ranks can stand in for any card.
+///
+/// Wild cards may duplicate natural cards when making pairs, trips, quads and
+/// five of a kind, which is what makes five of a kind possible. In a flush,
+/// wild cards only stand in for ranks missing from the suit, so `Ac Jc 6c`
+/// with two jokers is an A-K-Q-J-6 flush, not A-A-A-J-6.
+///
+/// # Panics
+///
+/// If fewer than five cards are given.
+pub fn best_hand(cards: &[PlayingCard], wild_ranks: &[Rank]) -> Hand {
+    assert!(cards.len() >= 5, "Need at least 5 cards to make a hand");
+    let mut natural: Vec<Card> = Vec::new();
+    let mut wilds = 0;
+    for &card in cards {
+        match card {
+            PlayingCard::Card(card) if !wild_ranks.contains(&card.0) => natural.push(card),
+            _ => wilds += 1,
+        }
+    }
+    natural.sort_by_key(|&(r, _)| Reverse(r));
+    let hand = Cards { natural, wilds };
+
+    if let Some(rank) = hand.is_five_of_a_kind() {
+        return FiveOfAKind(rank);
+    }
+    if hand.is_royal_flush() {
+        return RoyalFlush;
+    }
+    if let Some(rank) = hand.is_straight_flush() {
+        return StraightFlush(rank);
+    }
+    if let Some(ranks) = hand.is_four_of_a_kind() {
+        return FourOfAKind(ranks);
+    }
+    if let Some(ranks) = hand.is_full_house() {
+        return FullHouse(ranks);
+    }
+    if let Some(ranks) = hand.is_flush() {
+        return Flush(ranks);
+    }
+    if let Some(rank) = hand.is_straight() {
+        return Straight(rank);
+    }
+    if let Some(rank) = hand.of_a_kind(3) {
+        let [k1, k2] = hand.kickers(&[rank]);
+        return ThreeOfAKind([rank, k1, k2]);
+    }
+    if let Some(ranks) = hand.is_two_pair() {
+        return TwoPair(ranks);
+    }
+    if let Some(rank) = hand.of_a_kind(2) {
+        let [k1, k2, k3] = hand.kickers(&[rank]);
+        return OnePair([rank, k1, k2, k3]);
+    }
+    HighCard(hand.kickers(&[]))
+}
+
+/// Highest card of each straight, from highest to lowest. Five is the wheel.
+const STRAIGHTS: [Rank; 10] = [Ace, King, Queen, Jack, Ten, Nine, Eight, Seven, Six, Five];
+
+/// Natural cards sorted from highest to lowest rank, and the number of wild cards.
+///
+/// Each check only uses as many wild cards as it needs. Since the checks run
+/// from the best hand down, a check for a lower hand never has wild cards left
+/// over, otherwise a better hand would have been found.
+struct Cards {
+    natural: Vec<Card>,
+    wilds: usize,
+}
+
+impl Cards {
+    fn count(&self, rank: Rank) -> usize {
+        self.natural.iter().filter(|&&(r, _)| r == rank).count()
+    }
+
+    fn missing(&self, needed: impl IntoIterator<Item = Card>) -> usize {
+        needed
+            .into_iter()
+            .filter(|card| !self.natural.contains(card))
+            .count()
+    }
+
+    /// Highest rank that can be made `n` of a kind.
+    fn of_a_kind(&self, n: usize) -> Option<Rank> {
+        RANKS
+            .into_iter()
+            .rev()
+            .find(|&rank| self.count(rank) + self.wilds >= n)
+    }
+
+    /// Highest natural ranks, excluding the given ranks.
+    fn kickers<const N: usize>(&self, exclude: &[Rank]) -> [Rank; N] {
+        let kickers: Vec<Rank> = self
+            .natural
+            .iter()
+            .map(|&(r, _)| r)
+            .filter(|r| !exclude.contains(r))
+            .take(N)
+            .collect();
+        kickers.try_into().unwrap()
+    }
+
+    fn is_five_of_a_kind(&self) -> Option<Rank> {
+        self.of_a_kind(5)
+    }
+
+    fn is_royal_flush(&self) -> bool {
+        SUITS.into_iter().any(|suit| {
+            let needed = [Ace, King, Queen, Jack, Ten].map(|rank| (rank, suit));
+            self.missing(needed) <= self.wilds
+        })
+    }
+
+    fn is_straight_flush(&self) -> Option<Rank> {
+        STRAIGHTS.into_iter().skip(1).find(|&high| {
+            SUITS.into_iter().any(|suit| {
+                let needed = straight_ranks(high).map(|rank| (rank, suit));
+                self.missing(needed) <= self.wilds
+            })
+        })
+    }
+
+    fn is_four_of_a_kind(&self) -> Option<[Rank; 2]> {
+        let rank = self.of_a_kind(4)?;
+        let [kicker] = self.kickers(&[rank]);
+        Some([rank, kicker])
+    }
+
+    fn is_full_house(&self) -> Option<[Rank; 2]> {
+        let needed = |rank: Rank, n: usize| n.saturating_sub(self.count(rank));
+        for three in RANKS.into_iter().rev() {
+            for two in RANKS.into_iter().rev().filter(|&r| r != three) {
+                if needed(three, 3) + needed(two, 2) <= self.wilds {
+                    return Some([three, two]);
+                }
+            }
+        }
+        None
+    }
+
+    fn is_flush(&self) -> Option<FlushRanks> {
+        let mut best: Option<FlushRanks> = None;
+        for suit in SUITS {
+            let suited: Vec<Rank> = self
+                .natural
+                .iter()
+                .filter(|&&(_, s)| s == suit)
+                .map(|&(r, _)| r)
+                .collect();
+            // Wild cards become the highest ranks missing from the suit.
+            let mut ranks: Vec<Rank> = RANKS
+                .into_iter()
+                .rev()
+                .filter(|r| !suited.contains(r))
+                .take(self.wilds)
+                .chain(suited.iter().copied())
+                .collect();
+            if ranks.len() < 5 {
+                continue;
+            }
+            ranks.sort_by_key(|&r| Reverse(r));
+            let ranks: FlushRanks = ranks[0..5].try_into().unwrap();
+            if best.is_none_or(|best| cmp_ranks(&ranks, &best).is_gt()) {
+                best = Some(ranks);
+            }
+        }
+        best
+    }
+
+    fn is_straight(&self) -> Option<Rank> {
+        STRAIGHTS.into_iter().find(|&high| {
+            let missing = straight_ranks(high)
+                .into_iter()
+                .filter(|&rank| self.count(rank) == 0)
+                .count();
+            missing <= self.wilds
+        })
+    }
+
+    fn is_two_pair(&self) -> Option<[Rank; 3]> {
+        let pairs: Vec<Rank> = RANKS
+            .into_iter()
+            .rev()
+            .filter(|&rank| self.count(rank) >= 2)
+            .take(2)
+            .collect();
+        match pairs[..] {
+            [high, low] => {
+                let [kicker] = self.kickers(&[high, low]);
+                Some([high, low, kicker])
+            }
+            _ => None,
+        }
+    }
+}
+
+fn straight_ranks(high: Rank) -> [Rank; 5] {
+    match high {
+        Five => [Five, Four, Three, Two, Ace],
+        _ => {
+            let i = RANKS.iter().position(|&r| r == high).unwrap();
+            [
+                RANKS[i],
+                RANKS[i - 1],
+                RANKS[i - 2],
+                RANKS[i - 3],
+                RANKS[i - 4],
+            ]
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Suit isomorphism.
+//!
+//! Suits have no value in hold'em, so situations that only differ by a
+//! permutation of suits are strategically identical. These functions map such
+//! situations to a single canonical representative.
+
+use crate::card::{deck_52, Card, Rank, Suit, SUITS};
+use std::{cmp::Reverse, collections::BTreeMap, fmt};
+
+/// Canonical form of hole cards and board under suit permutation.
+///
+/// Two situations are isomorphic exactly when their canonical forms are equal.
+/// Cards are sorted from highest to lowest rank within the hole cards and within
+/// the board, and the suits are relabelled to the permutation that gives the
+/// smallest result, so the first suit used is always clubs.
+pub fn canonicalize(hole: &[Card], board: &[Card]) -> (Vec<Card>, Vec<Card>) {
+    suit_permutations()
+        .into_iter()
+        .map(|permutation| (permute(hole, permutation), permute(board, permutation)))
+        .min()
+        .unwrap()
+}
+
+/// Strategically distinct flops, with the number of flops each one represents.
+///
+/// There are 1755 canonical flops, and the weights add up to the 22100 possible flops.
+pub fn canonical_flops() -> Vec<([Card; 3], u32)> {
+    let deck = deck_52();
+    let mut flops: BTreeMap<Vec<Card>, u32> = BTreeMap::new();
+    for a in 0..deck.len() {
+        for b in (a + 1)..deck.len() {
+            for c in (b + 1)..deck.len() {
+                let (_, flop) = canonicalize(&[], &[deck[a], deck[b], deck[c]]);
+                *flops.entry(flop).or_default() += 1;
+            }
+        }
+    }
+    flops
+        .into_iter()
+        .map(|(flop, weight)| (flop.try_into().unwrap(), weight))
+        .collect()
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// One of the 169 preflop starting hand classes, such as `AKs`, `T9o` or `77`.
+pub struct StartingHand {
+    pub high: Rank,
+    pub low: Rank,
+    /// Always false for pairs.
+    pub suited: bool,
+}
+
+impl StartingHand {
+    pub fn of(hole: [Card; 2]) -> Self {
+        let [(ra, sa), (rb, sb)] = hole;
+        Self {
+            high: ra.max(rb),
+            low: ra.min(rb),
+            suited: sa == sb,
+        }
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /// Hole card combinations in this class: 6 for pairs, 4 for suited hands and 12 for offsuit hands.
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::new();
+        for (i, &sa) in SUITS.iter().enumerate() {
+            for (j, &sb) in SUITS.iter().enumerate() {
+                let wanted = match (self.is_pair(), self.suited) {
+                    (true, _) => i < j,
+                    (false, true) => i == j,
+                    (false, false) => i != j,
+                };
+                if wanted {
+                    combos.push([(self.high, sa), (self.low, sb)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl fmt::Display for StartingHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.high.to_char(), self.low.to_char())?;
+        match (self.is_pair(), self.suited) {
+            (true, _) => Ok(()),
+            (false, true) => write!(f, "s"),
+            (false, false) => write!(f, "o"),
+        }
+    }
+}
+
+/// All 1326 hole card combinations grouped into the 169 starting hand classes.
+pub fn starting_hands() -> BTreeMap<StartingHand, Vec<[Card; 2]>> {
+    let deck = deck_52();
+    let mut classes: BTreeMap<StartingHand, Vec<[Card; 2]>> = BTreeMap::new();
+    for a in 0..deck.len() {
+        for b in (a + 1)..deck.len() {
+            let hole = [deck[a], deck[b]];
+            classes
+                .entry(StartingHand::of(hole))
+                .or_default()
+                .push(hole);
+        }
+    }
+    classes
+}
+
+fn permute(cards: &[Card], permutation: [Suit; 4]) -> Vec<Card> {
+    let mut cards: Vec<Card> = cards
+        .iter()
+        .map(|&(rank, suit)| (rank, permutation[suit as usize]))
+        .collect();
+    cards.sort_by_key(|&(rank, suit)| (Reverse(rank), suit));
+    cards
+}
+
+fn suit_permutations() -> Vec<[Suit; 4]> {
+    let mut permutations = Vec::new();
+    for a in SUITS {
+        for b in SUITS.into_iter().filter(|&b| b != a) {
+            for c in SUITS.into_iter().filter(|&c| c != a && c != b) {
+                for d in SUITS.into_iter().filter(|&d| d != a && d != b && d != c) {
+                    permutations.push([a, b, c, d]);
+                }
+            }
+        }
+    }
+    permutations
+}
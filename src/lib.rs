@@ -0,0 +1,18 @@
+//! Texas hold'em: cards and hand evaluation, equity calculation, and a game
+//! engine that runs cash game tables.
+
+pub mod card;
+pub mod dealer;
+pub mod equity;
+pub mod game;
+pub mod hand;
+pub mod isomorphism;
+pub mod player;
+
+pub use card::{Card, Rank, Suit};
+pub use game::{betting::Action, Game, GameOptions};
+pub use hand::{Hand, Variant};
+pub use player::Player;
+
+#[cfg(test)]
+mod test;
@@ -1,10 +0,0 @@
-mod card;
-mod dealer;
-mod game;
-mod hand;
-mod player;
-
-#[cfg(test)]
-mod test;
-
-fn main() {}
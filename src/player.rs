@@ -2,26 +2,56 @@ use std::cmp::min;
 
 use crate::card::Card;
 
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Status {
+    /// In the hand with chips left to bet, or ready to be dealt into the next one.
+    Active,
+    Folded,
+    AllIn,
+    /// Not dealt in until they sit back in.
+    SittingOut,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
+    /// Identifies the player across hands and tables.
+    pub id: u64,
+    /// Name shown to other players.
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::card::as_string::cards"))]
     pub cards: Vec<Card>,
     pub chips: u64,
     pub bet: u64,
+    pub status: Status,
+    pub missed_small_blind: bool,
+    pub missed_big_blind: bool,
 }
 
 impl Player {
-    pub fn new(chips: u64) -> Self {
+    pub fn new(id: u64, name: &str, chips: u64) -> Self {
         Self {
+            id,
+            name: String::from(name),
             cards: Vec::new(),
             chips,
             bet: 0,
+            status: Status::Active,
+            missed_small_blind: false,
+            missed_big_blind: false,
         }
     }
 
-    pub fn bet(&mut self, mut bet: u64) {
+    /// Adds to the player's bet, up to their stack, and returns the chips added.
+    pub fn bet(&mut self, mut bet: u64) -> u64 {
         bet = min(bet, self.chips);
         self.chips -= bet;
         self.bet += bet;
+        if self.chips == 0 && self.status == Status::Active {
+            self.status = Status::AllIn;
+        }
+        bet
     }
 
     pub fn take_cards(&mut self, cards: Vec<Card>) {
@@ -31,4 +61,19 @@ impl Player {
     pub fn muck(&mut self) {
         self.cards.clear()
     }
+
+    /// Takes chips for the pot that do not count as a bet, up to the player's stack.
+    pub fn post_dead(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.chips);
+        self.chips -= amount;
+        if self.chips == 0 && self.status == Status::Active {
+            self.status = Status::AllIn;
+        }
+        amount
+    }
+
+    pub fn fold(&mut self) {
+        self.muck();
+        self.status = Status::Folded;
+    }
 }
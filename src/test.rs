@@ -1,3 +1,7 @@
+mod evaluator;
+mod game;
+mod history;
+
 use crate::card::{Rank::*, Suit::*};
 use crate::hand::five_card_hand::best_hand;
 
@@ -14,3 +18,261 @@ fn scratch() {
         ])
     )
 }
+
+#[test]
+fn short_deck() {
+    use crate::card::deck_36;
+    use crate::hand::{Hand::*, Variant::*};
+
+    assert_eq!(deck_36().len(), 36);
+    let wheel = ShortDeck.best_hand([
+        (Ace, Clubs),
+        (Six, Hearts),
+        (Seven, Spades),
+        (Eight, Clubs),
+        (Nine, Diamonds),
+        (King, Clubs),
+        (King, Hearts),
+    ]);
+    assert_eq!(wheel, Straight(Nine));
+    let flush = Flush([Ace, King, Ten, Eight, Six]);
+    let full_house = FullHouse([Ace, King]);
+    assert!(ShortDeck.cmp_hands(&flush, &full_house).is_gt());
+    assert!(Standard.cmp_hands(&flush, &full_house).is_lt());
+}
+
+#[test]
+fn wild_cards() {
+    use crate::card::{deck, DeckOptions, PlayingCard::*};
+    use crate::dealer::Dealer;
+    use crate::hand::{seven_card_hand, wild_hand, Hand::*};
+
+    let options = DeckOptions {
+        jokers: 2,
+        wild_ranks: vec![Two],
+    };
+    assert_eq!(deck(&options).len(), 54);
+    let mut dealer = Dealer::with_deck(deck(&options));
+    let dealt = dealer.deal(54);
+    assert_eq!(dealt.iter().filter(|&&card| card == Joker).count(), 2);
+    assert!(options.is_wild(Card((Two, Spades))));
+    assert!(options.is_wild(Joker));
+
+    let five_aces = wild_hand::best_hand(
+        &[
+            Card((Ace, Clubs)),
+            Card((Ace, Hearts)),
+            Card((Ace, Spades)),
+            Card((Ace, Diamonds)),
+            Joker,
+        ],
+        &options.wild_ranks,
+    );
+    assert_eq!(five_aces, FiveOfAKind(Ace));
+    assert!(five_aces > RoyalFlush);
+
+    let straight_flush = wild_hand::best_hand(
+        &[
+            Card((Nine, Hearts)),
+            Card((Eight, Hearts)),
+            Card((Two, Clubs)),
+            Card((Six, Hearts)),
+            Card((King, Spades)),
+            Joker,
+            Card((Three, Diamonds)),
+        ],
+        &options.wild_ranks,
+    );
+    assert_eq!(straight_flush, StraightFlush(Ten));
+
+    // Wild cards in a flush only fill ranks missing from the suit.
+    let flush = wild_hand::best_hand(
+        &[
+            Card((Ace, Clubs)),
+            Card((Jack, Clubs)),
+            Card((Six, Clubs)),
+            Card((Seven, Spades)),
+            Joker,
+            Joker,
+        ],
+        &[],
+    );
+    assert_eq!(flush, Flush([Ace, King, Queen, Jack, Six]));
+
+    let cards = [
+        (King, Clubs),
+        (King, Hearts),
+        (Seven, Spades),
+        (Seven, Clubs),
+        (Four, Diamonds),
+        (Four, Hearts),
+        (Two, Clubs),
+    ];
+    assert_eq!(
+        wild_hand::best_hand(&cards.map(Card), &[]),
+        seven_card_hand::best_hand(cards)
+    );
+    assert_eq!(
+        wild_hand::best_hand(&cards.map(Card), &[Two]),
+        FullHouse([King, Seven])
+    );
+}
+
+#[test]
+fn equity_on_the_river() {
+    use crate::equity::{equity, Equity};
+
+    let board = [
+        (Two, Hearts),
+        (Seven, Diamonds),
+        (Nine, Spades),
+        (Jack, Clubs),
+        (Four, Hearts),
+    ];
+    let hands = [
+        [(Ace, Spades), (King, Spades)],
+        [(Queen, Diamonds), (Queen, Clubs)],
+        [(Ace, Clubs), (King, Hearts)],
+    ];
+    let results = equity(&hands, &board, &[], 10).unwrap();
+    assert_eq!(results[1].wins, 10);
+    assert_eq!(results[0].equity(), 0.0);
+    assert!(equity(&hands, &board, &[(Ace, Spades)], 10).is_err());
+    assert!(equity(&hands, &board, &[], 0).is_err());
+    assert_eq!(Equity::default().equity(), 0.0);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_equity() {
+    use crate::equity::par_equity;
+    use crate::hand::seven_card_hand::{best_hands, par_best_hands};
+
+    let hands = [
+        [(Ace, Spades), (Ace, Hearts)],
+        [(King, Diamonds), (King, Clubs)],
+    ];
+    let results = par_equity(&hands, &[], &[], 20_000).unwrap();
+    assert_eq!(results[0].trials, 20_000);
+    assert!((results[0].equity() - 0.82).abs() < 0.02);
+
+    let cards = [[
+        (Ace, Spades),
+        (Ace, Hearts),
+        (Two, Hearts),
+        (Seven, Diamonds),
+        (Nine, Spades),
+        (Jack, Clubs),
+        (Four, Hearts),
+    ]; 100];
+    assert_eq!(best_hands(&cards), par_best_hands(&cards));
+}
+
+#[test]
+fn suit_isomorphism() {
+    use crate::isomorphism::{canonical_flops, canonicalize, starting_hands, StartingHand};
+
+    let flops = canonical_flops();
+    assert_eq!(flops.len(), 1755);
+    assert_eq!(flops.iter().map(|&(_, weight)| weight).sum::<u32>(), 22100);
+
+    let classes = starting_hands();
+    assert_eq!(classes.len(), 169);
+    assert_eq!(classes.values().map(Vec::len).sum::<usize>(), 1326);
+    for (class, combos) in &classes {
+        assert_eq!(&class.combos().len(), &combos.len(), "{class}");
+    }
+    let ako = StartingHand::of([(King, Hearts), (Ace, Spades)]);
+    assert_eq!(ako.to_string(), "AKo");
+    assert_eq!(ako.combos().len(), 12);
+
+    let a = canonicalize(
+        &[(Ace, Spades), (King, Spades)],
+        &[(Two, Spades), (Seven, Hearts), (Nine, Diamonds)],
+    );
+    let b = canonicalize(
+        &[(King, Hearts), (Ace, Hearts)],
+        &[(Nine, Clubs), (Two, Hearts), (Seven, Diamonds)],
+    );
+    assert_eq!(a, b);
+    assert_eq!(a.0, vec![(Ace, Clubs), (King, Clubs)]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    use crate::card::{as_string::CardString, PlayingCard};
+    use crate::game::{betting::Action::*, Game};
+    use crate::hand::Hand::*;
+    use game::{game, options};
+
+    let card = serde_json::to_string(&PlayingCard::Card((Ace, Hearts))).unwrap();
+    assert_eq!(card, r#"{"Card":"Ah"}"#);
+    let hand = serde_json::to_string(&TwoPair([King, Seven, Ace])).unwrap();
+    assert_eq!(hand, r#"{"TwoPair":["K","7","A"]}"#);
+    assert!(serde_json::from_str::<PlayingCard>(r#"{"Card":"Ax"}"#).is_err());
+    let card = serde_json::to_string(&CardString((Ace, Hearts))).unwrap();
+    assert_eq!(card, r#""Ah""#);
+    let card: CardString = serde_json::from_str(r#""Td""#).unwrap();
+    assert_eq!(card, CardString((Ten, Diamonds)));
+
+    let mut played = game(options(Default::default()), &[100, 100, 100]);
+    played.set_seed(7);
+    played.start_hand().unwrap();
+    played.act(Call).unwrap();
+    let json = serde_json::to_string(&played).unwrap();
+    let mut restored: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    for action in [Call, Check, Bet(10), Call, Call] {
+        played.act(action).unwrap();
+        restored.act(action).unwrap();
+    }
+    assert_eq!(restored.board(), played.board());
+    assert_eq!(
+        restored.player(0).unwrap().cards,
+        played.player(0).unwrap().cards
+    );
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot() {
+    use crate::game::{betting::Action::*, snapshot::SNAPSHOT_VERSION, Game};
+    use game::{game, options};
+
+    let mut played = game(options(Default::default()), &[100, 100, 100]);
+    played.set_seed(3);
+    played.start_hand().unwrap();
+    for action in [Call, Call, Check, Bet(4)] {
+        played.act(action).unwrap();
+    }
+    let mut restored = Game::restore_bytes(&played.snapshot_bytes().unwrap()).unwrap();
+    assert_eq!(restored.snapshot(), played.snapshot());
+    assert_eq!(restored.turn(), played.turn());
+    assert_eq!(restored.pot(), played.pot());
+    for game in [&mut played, &mut restored] {
+        for action in [Call, Fold, Check, Check, Check, Check] {
+            game.act(action).unwrap();
+        }
+        // Later hands are shuffled the same way too.
+        game.start_hand().unwrap();
+    }
+    let awards = |game: &Game| game.histories()[0].awards.clone();
+    assert_eq!(awards(&restored), awards(&played));
+    for position in 0..3 {
+        let cards = |game: &Game| game.player(position).unwrap().cards.clone();
+        assert_eq!(cards(&restored), cards(&played));
+    }
+
+    let snapshot = played.snapshot().unwrap();
+    let newer = snapshot.replacen(
+        &format!(r#""version":{SNAPSHOT_VERSION}"#),
+        r#""version":99"#,
+        1,
+    );
+    assert_eq!(
+        Game::restore(&newer).unwrap_err(),
+        "Snapshot version 99 is newer than the supported version 1"
+    );
+    assert!(Game::restore(r#"{"game":{}}"#).is_err());
+}
@@ -0,0 +1,152 @@
+use crate::card::{deck_52, Card, PlayingCard, Rank::*, Suit::*};
+use crate::hand::{five_card_hand, seven_card_hand, wild_hand, Hand, Hand::*};
+use proptest::prelude::*;
+use rand::seq::SliceRandom;
+
+/// Index of the hand category, from high card to royal flush.
+fn category(hand: &Hand) -> usize {
+    match hand {
+        HighCard(_) => 0,
+        OnePair(_) => 1,
+        TwoPair(_) => 2,
+        ThreeOfAKind(_) => 3,
+        Straight(_) => 4,
+        Flush(_) => 5,
+        FullHouse(_) => 6,
+        FourOfAKind(_) => 7,
+        StraightFlush(_) => 8,
+        RoyalFlush => 9,
+        FiveOfAKind(_) => 10,
+    }
+}
+
+fn best_of_subsets(cards: [Card; 7]) -> Hand {
+    let mut best: Option<Hand> = None;
+    for skip_a in 0..7 {
+        for skip_b in (skip_a + 1)..7 {
+            let five: Vec<Card> = (0..7)
+                .filter(|&i| i != skip_a && i != skip_b)
+                .map(|i| cards[i])
+                .collect();
+            let hand = five_card_hand::best_hand(five.try_into().unwrap());
+            if best.as_ref().is_none_or(|best| hand > *best) {
+                best = Some(hand);
+            }
+        }
+    }
+    best.unwrap()
+}
+
+#[test]
+fn five_card_category_counts() {
+    let deck = deck_52();
+    let mut counts = [0u32; 10];
+    for a in 0..52 {
+        for b in (a + 1)..52 {
+            for c in (b + 1)..52 {
+                for d in (c + 1)..52 {
+                    for e in (d + 1)..52 {
+                        let hand = five_card_hand::best_hand([
+                            deck[a], deck[b], deck[c], deck[d], deck[e],
+                        ]);
+                        counts[category(&hand)] += 1;
+                    }
+                }
+            }
+        }
+    }
+    assert_eq!(
+        counts,
+        [1302540, 1098240, 123552, 54912, 10200, 5108, 3744, 624, 36, 4]
+    );
+}
+
+#[test]
+fn seven_card_matches_best_five_card_subset() {
+    let mut deck = deck_52();
+    let mut rng = rand::thread_rng();
+    for _ in 0..20_000 {
+        deck.shuffle(&mut rng);
+        let cards: [Card; 7] = deck[0..7].try_into().unwrap();
+        assert_eq!(
+            seven_card_hand::best_hand(cards),
+            best_of_subsets(cards),
+            "{cards:?}"
+        );
+    }
+}
+
+#[test]
+fn steel_wheel() {
+    let cards = [
+        (Ace, Clubs),
+        (Five, Clubs),
+        (Four, Clubs),
+        (Three, Clubs),
+        (Two, Clubs),
+    ];
+    assert_eq!(five_card_hand::best_hand(cards), StraightFlush(Five));
+    assert!(five_card_hand::best_hand(cards) < StraightFlush(Six));
+}
+
+fn seven_cards() -> impl Strategy<Value = [Card; 7]> {
+    Just(deck_52())
+        .prop_shuffle()
+        .prop_map(|deck| deck[0..7].try_into().unwrap())
+}
+
+proptest! {
+    #[test]
+    fn seven_card_matches_subsets(cards in seven_cards()) {
+        prop_assert_eq!(seven_card_hand::best_hand(cards), best_of_subsets(cards));
+    }
+
+    #[test]
+    fn wild_hand_without_wilds_matches_seven_card(cards in seven_cards()) {
+        let playing_cards = cards.map(PlayingCard::Card);
+        prop_assert_eq!(
+            wild_hand::best_hand(&playing_cards, &[]),
+            seven_card_hand::best_hand(cards)
+        );
+    }
+
+    #[test]
+    fn wheel_is_at_least_five_high_straight(
+        extra in Just(deck_52()).prop_shuffle(),
+        suits in prop::array::uniform5(0..4usize),
+    ) {
+        let suit = |i: usize| [Clubs, Diamonds, Hearts, Spades][suits[i]];
+        let wheel = [
+            (Ace, suit(0)),
+            (Two, suit(1)),
+            (Three, suit(2)),
+            (Four, suit(3)),
+            (Five, suit(4)),
+        ];
+        let others: Vec<Card> = extra.into_iter().filter(|c| !wheel.contains(c)).take(2).collect();
+        let cards: [Card; 7] = [&wheel[..], &others[..]].concat().try_into().unwrap();
+        let hand = seven_card_hand::best_hand(cards);
+        prop_assert!(hand >= Straight(Five));
+        prop_assert_eq!(&hand, &best_of_subsets(cards));
+    }
+
+    #[test]
+    fn two_trips_make_full_house(
+        ranks in Just(crate::card::RANKS.to_vec()).prop_shuffle(),
+        extra_suit in 0..4usize,
+    ) {
+        let (high, low) = (ranks[0].max(ranks[1]), ranks[0].min(ranks[1]));
+        let kicker = ranks[2];
+        let cards = [
+            (high, Clubs),
+            (high, Diamonds),
+            (high, Hearts),
+            (low, Clubs),
+            (low, Diamonds),
+            (low, Spades),
+            (kicker, [Clubs, Diamonds, Hearts, Spades][extra_suit]),
+        ];
+        prop_assert_eq!(seven_card_hand::best_hand(cards), FullHouse([high, low]));
+        prop_assert_eq!(five_card_hand::best_hand(cards[0..5].try_into().unwrap()), FullHouse([high, low]));
+    }
+}
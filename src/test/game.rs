@@ -0,0 +1,755 @@
+use crate::card::parse_cards;
+use crate::game::{
+    betting::{Action::*, BettingStructure},
+    event::GameEvent,
+    history::{self, Post, PostKind},
+    ledger::Transaction::{self, Award, Rebuy, Returned, TopUp},
+    view::GameView,
+    Game, GameOptions, Rake, Straddle, Street,
+};
+use crate::hand::Variant;
+use crate::player::{Player, Status};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub fn options(betting: BettingStructure) -> GameOptions {
+    GameOptions {
+        num_seats: 6,
+        min_buyin: 1,
+        max_buyin: 1000,
+        small_blind: 1,
+        big_blind: 2,
+        variant: Variant::Standard,
+        betting,
+        ante: 0,
+        big_blind_ante: false,
+        straddle: Straddle::Off,
+        rake: Rake::default(),
+    }
+}
+
+pub fn game(options: GameOptions, stacks: &[u64]) -> Game {
+    let mut game = Game::new(options).unwrap();
+    for (position, &chips) in stacks.iter().enumerate() {
+        let name = format!("Player {position}");
+        game.seat_player(position, Player::new(position as u64, &name, chips))
+            .unwrap();
+    }
+    game
+}
+
+fn total_chips(game: &Game) -> u64 {
+    let stacks: u64 = (0..6)
+        .filter_map(|p| game.player(p))
+        .map(|p| p.chips + p.bet)
+        .sum();
+    stacks + game.pot()
+}
+
+#[test]
+fn no_limit() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.turn(), 0);
+    assert_eq!(game.raise_range(), Some((4, 200)));
+    assert!(game.act(Raise(3)).is_err());
+    assert!(game.act(Bet(10)).is_err());
+    assert!(game.act(Check).is_err());
+    game.act(Raise(6)).unwrap();
+    game.act(Fold).unwrap();
+    game.act(Call).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    assert_eq!(game.board().len(), 3);
+    assert_eq!(game.pot(), 13);
+    assert_eq!(game.turn(), 2);
+    game.act(Check).unwrap();
+    game.act(Bet(20)).unwrap();
+    game.act(Fold).unwrap();
+    assert_eq!(game.street(), None);
+    assert_eq!(game.player(0).unwrap().chips, 207);
+    assert_eq!(total_chips(&game), 600);
+}
+
+#[test]
+fn pot_limit() {
+    let mut game = game(options(BettingStructure::PotLimit), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.raise_range(), Some((4, 7)));
+    assert!(game.act(Raise(8)).is_err());
+    game.act(Raise(7)).unwrap();
+    // Pot of 10 plus 6 to call.
+    assert_eq!(game.raise_range(), Some((12, 23)));
+}
+
+#[test]
+fn fixed_limit() {
+    let betting = BettingStructure::FixedLimit {
+        small_bet: 2,
+        big_bet: 4,
+        max_raises: 4,
+    };
+    let mut game = game(options(betting), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert!(game.act(Raise(5)).is_err());
+    game.act(Raise(4)).unwrap();
+    game.act(Raise(6)).unwrap();
+    game.act(Raise(8)).unwrap();
+    assert_eq!(game.raise_range(), None);
+    assert!(game.act(Raise(10)).is_err());
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    assert_eq!(game.raise_range(), Some((2, 2)));
+    game.act(Check).unwrap();
+    game.act(Check).unwrap();
+    game.act(Check).unwrap();
+    assert_eq!(game.street(), Some(Street::Turn));
+    assert_eq!(game.raise_range(), Some((4, 4)));
+}
+
+#[test]
+fn spread_limit() {
+    let betting = BettingStructure::SpreadLimit {
+        min_bet: 2,
+        max_bet: 10,
+    };
+    let mut game = game(options(betting), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.raise_range(), Some((4, 12)));
+    assert!(game.act(Raise(13)).is_err());
+    game.act(Raise(12)).unwrap();
+}
+
+#[test]
+fn manual_showdown() {
+    let mut game = game(options(BettingStructure::NoLimit), &[100, 100]);
+    game.stack_deck(parse_cards("AhAd KhKd 2c7s9d3h8c").unwrap())
+        .unwrap();
+    game.start_hand().unwrap();
+    assert_eq!(
+        game.showdown(),
+        Err(String::from("Showdown needs 5 board cards, board has 0"))
+    );
+    game.act(Call).unwrap();
+    game.deal_to_board(5);
+    assert_eq!(game.showdown(), Ok(vec![0]));
+    assert_eq!(game.street(), None);
+    assert_eq!(game.player(0).unwrap().chips, 102);
+    assert_eq!(game.player(1).unwrap().chips, 98);
+    assert_eq!(game.check_chips(), Ok(()));
+    assert_eq!(game.showdown(), Err(String::from("No hand in progress")));
+}
+
+#[test]
+fn side_pots() {
+    let mut game = game(options(BettingStructure::NoLimit), &[50, 100, 200]);
+    game.stack_deck(parse_cards("AhAd KhKd QhQd 2c7s9d 3h 8c").unwrap())
+        .unwrap();
+    game.start_hand().unwrap();
+    game.act(AllIn).unwrap();
+    game.act(AllIn).unwrap();
+    game.act(AllIn).unwrap();
+    assert_eq!(game.street(), None);
+    assert_eq!(game.board().len(), 5);
+    assert_eq!(game.pot(), 0);
+    // Aces win the main pot of 150, kings the side pot of 100, and the
+    // uncalled 100 is returned to the big stack.
+    let stacks: Vec<u64> = (0..3).map(|p| game.player(p).unwrap().chips).collect();
+    assert_eq!(stacks, [150, 100, 100]);
+}
+
+#[test]
+fn minimum_raise() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    game.act(Raise(10)).unwrap();
+    assert_eq!(game.raise_range(), Some((18, 200)));
+    assert!(game.act(Raise(17)).is_err());
+    game.act(Raise(30)).unwrap();
+    assert_eq!(game.raise_range(), Some((50, 200)));
+}
+
+#[test]
+fn short_all_in_does_not_reopen_betting() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 15, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.turn(), 3);
+    game.act(Raise(10)).unwrap();
+    game.act(Call).unwrap();
+    // Small blind raises all in by 5, less than the full raise of 8.
+    game.act(AllIn).unwrap();
+    // The big blind has not acted yet, so they can still raise.
+    assert_eq!(game.turn(), 2);
+    assert_eq!(game.raise_range(), Some((23, 200)));
+    game.act(Call).unwrap();
+    // Players who already acted can only call or fold.
+    assert_eq!(game.turn(), 3);
+    assert_eq!(game.raise_range(), None);
+    assert!(game.act(Raise(30)).is_err());
+    assert!(game.act(AllIn).is_err());
+    game.act(Call).unwrap();
+    assert_eq!(game.raise_range(), None);
+    game.act(Call).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    assert_eq!(game.pot(), 60);
+}
+
+#[test]
+fn short_all_ins_that_add_up_to_a_full_raise_reopen_betting() {
+    let mut game = game(options(BettingStructure::NoLimit), &[15, 19, 200, 200]);
+    game.start_hand().unwrap();
+    game.act(Raise(10)).unwrap();
+    game.act(AllIn).unwrap();
+    game.act(AllIn).unwrap();
+    game.act(Call).unwrap();
+    // The bet went up by 9 since the raise to 10, more than the full raise of 8.
+    assert_eq!(game.turn(), 3);
+    assert_eq!(game.raise_range(), Some((27, 200)));
+    game.act(Raise(40)).unwrap();
+    game.act(Fold).unwrap();
+    // Everyone else is all in, so the board is run out.
+    assert_eq!(game.street(), None);
+    assert_eq!(game.board().len(), 5);
+    assert_eq!(total_chips(&game), 434);
+}
+
+#[test]
+fn antes() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.ante = 1;
+    let mut game = game(options, &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.pot(), 3);
+    assert_eq!(game.player(2).unwrap().chips, 197);
+    game.act(Fold).unwrap();
+    game.act(Fold).unwrap();
+    assert_eq!(game.player(2).unwrap().chips, 203);
+    assert_eq!(total_chips(&game), 600);
+}
+
+#[test]
+fn big_blind_ante() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.ante = 2;
+    options.big_blind_ante = true;
+    let mut game = game(options, &[200, 200, 3]);
+    game.start_hand().unwrap();
+    // The big blind is posted before the ante.
+    assert_eq!(game.pot(), 1);
+    assert_eq!(game.player(2).unwrap().chips, 0);
+    game.act(AllIn).unwrap();
+    game.act(Fold).unwrap();
+    assert_eq!(game.street(), None);
+    assert_eq!(total_chips(&game), 403);
+}
+
+#[test]
+fn under_the_gun_straddle() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.straddle = Straddle::UnderTheGun;
+    let mut game = game(options, &[200, 200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.turn(), 0);
+    assert_eq!(game.to_call(), 4);
+    assert_eq!(game.raise_range(), Some((8, 200)));
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    // The straddler has the option.
+    assert_eq!(game.turn(), 3);
+    assert_eq!(game.street(), Some(Street::Preflop));
+    game.act(Check).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    assert_eq!(game.pot(), 16);
+}
+
+#[test]
+fn button_straddle() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.straddle = Straddle::Button;
+    let mut game = game(options, &[200, 200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(game.turn(), 1);
+    assert_eq!(game.to_call(), 3);
+    game.act(Fold).unwrap();
+    game.act(Call).unwrap();
+    assert_eq!(game.turn(), 0);
+    game.act(Raise(12)).unwrap();
+    assert_eq!(game.turn(), 2);
+}
+
+fn fold_around(game: &mut Game) {
+    while game.street().is_some() {
+        game.act(Fold).unwrap();
+    }
+}
+
+fn bet(game: &Game, position: usize) -> u64 {
+    game.player(position).unwrap().bet
+}
+
+#[test]
+fn dead_button() {
+    let mut game = game(
+        options(BettingStructure::NoLimit),
+        &[200, 200, 200, 200, 200],
+    );
+    game.start_hand().unwrap();
+    fold_around(&mut game);
+    // The small blind leaves, so the button stays on their empty seat.
+    game.remove_player(1).unwrap();
+    game.start_hand().unwrap();
+    assert_eq!(game.button(), 1);
+    assert_eq!((bet(&game, 2), bet(&game, 3)), (1, 2));
+    assert_eq!(game.turn(), 4);
+    fold_around(&mut game);
+    // The last big blind leaves, so there is no small blind.
+    game.remove_player(3).unwrap();
+    game.start_hand().unwrap();
+    assert_eq!(game.button(), 2);
+    assert_eq!((bet(&game, 2), bet(&game, 4)), (0, 2));
+    assert_eq!(game.turn(), 0);
+}
+
+#[test]
+fn heads_up_transition() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    fold_around(&mut game);
+    game.remove_player(0).unwrap();
+    game.start_hand().unwrap();
+    // The last big blind does not post it twice in a row.
+    assert_eq!(game.button(), 2);
+    assert_eq!((bet(&game, 2), bet(&game, 1)), (1, 2));
+    // The button acts first preflop and last after the flop.
+    assert_eq!(game.turn(), 2);
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    assert_eq!(game.turn(), 1);
+}
+
+#[test]
+fn heads_up_to_three_handed() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.remove_player(1).unwrap();
+    game.start_hand().unwrap();
+    assert_eq!((game.button(), bet(&game, 0), bet(&game, 2)), (0, 1, 2));
+    fold_around(&mut game);
+    game.seat_player(1, Player::new(1, "Player 1", 200))
+        .unwrap();
+    game.start_hand().unwrap();
+    // The button would be the new player's, so they wait for the big blind.
+    assert_eq!(game.button(), 2);
+    assert_eq!((bet(&game, 2), bet(&game, 0), bet(&game, 1)), (1, 2, 0));
+    assert!(game.player(1).unwrap().cards.is_empty());
+    assert_eq!(game.player(1).unwrap().status, Status::Folded);
+    fold_around(&mut game);
+    game.start_hand().unwrap();
+    // The big blind reaches them in order.
+    assert_eq!(game.button(), 2);
+    assert_eq!((bet(&game, 0), bet(&game, 1), bet(&game, 2)), (1, 2, 0));
+    assert_eq!(game.player(1).unwrap().cards.len(), 2);
+    fold_around(&mut game);
+    game.start_hand().unwrap();
+    // And then the small blind, never the big blind twice.
+    assert_eq!(game.button(), 0);
+    assert_eq!((bet(&game, 1), bet(&game, 2), bet(&game, 0)), (1, 2, 0));
+    assert_eq!(game.check_chips(), Ok(()));
+}
+
+#[test]
+fn missed_blinds() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200, 200]);
+    game.start_hand().unwrap();
+    fold_around(&mut game);
+    game.sit_out(3).unwrap();
+    game.start_hand().unwrap();
+    // The big blind skips the player sitting out.
+    assert_eq!((bet(&game, 2), bet(&game, 0)), (1, 2));
+    assert!(game.player(3).unwrap().cards.is_empty());
+    assert_eq!(game.player(3).unwrap().status, Status::SittingOut);
+    assert!(game.player(3).unwrap().missed_big_blind);
+    fold_around(&mut game);
+    game.sit_in(3).unwrap();
+    game.start_hand().unwrap();
+    // Back in, they post a live big blind and a dead small blind.
+    assert_eq!((bet(&game, 0), bet(&game, 1)), (1, 2));
+    assert_eq!(bet(&game, 3), 2);
+    assert_eq!(game.player(3).unwrap().chips, 197);
+    assert_eq!(game.pot(), 1);
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    assert_eq!(game.pot(), 9);
+}
+
+#[test]
+fn player_status() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 50, 200, 200]);
+    game.start_hand().unwrap();
+    // Button 0, blinds 1 and 2, so seat 3 is first to act.
+    assert_eq!(game.turn(), 3);
+    game.act(Fold).unwrap();
+    assert_eq!(game.player(3).unwrap().status, Status::Folded);
+    assert!(game.sit_out(0).is_err());
+    game.act(Call).unwrap();
+    game.act(AllIn).unwrap();
+    assert_eq!(game.player(1).unwrap().status, Status::AllIn);
+    // The all-in player is skipped.
+    assert_eq!(game.turn(), 2);
+    game.timeout().unwrap();
+    assert_eq!(game.player(2).unwrap().status, Status::SittingOut);
+    assert_eq!(game.turn(), 0);
+    game.act(Call).unwrap();
+    assert_eq!(game.street(), None);
+    game.start_hand().unwrap();
+    assert!(game.player(2).unwrap().cards.is_empty());
+    assert!(game.sit_in(2).is_ok());
+    assert_eq!(total_chips(&game), 650);
+}
+
+#[test]
+fn player_identity() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200]);
+    assert_eq!(game.seat_of(1), Some(1));
+    assert_eq!(game.player(1).unwrap().name, "Player 1");
+    assert_eq!(game.seat_of(5), None);
+    assert!(game
+        .seat_player(4, Player::new(1, "Player 1", 200))
+        .is_err());
+    game.remove_player(1).unwrap();
+    game.seat_player(4, Player::new(1, "Player 1", 200))
+        .unwrap();
+    assert_eq!(game.seat_of(1), Some(4));
+}
+
+#[test]
+fn seat_during_hand() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200]);
+    game.start_hand().unwrap();
+    assert_eq!(
+        game.seat_player(3, Player::new(3, "Player 3", 200)),
+        Err(String::from("Cannot seat a player during a hand"))
+    );
+    assert!(game.player(3).is_none());
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    assert_eq!(game.street(), Some(Street::Flop));
+    fold_around(&mut game);
+    game.seat_player(3, Player::new(3, "Player 3", 200))
+        .unwrap();
+    assert_eq!(game.check_chips(), Ok(()));
+}
+
+#[test]
+fn rebuys_and_top_ups() {
+    let mut game = game(options(BettingStructure::NoLimit), &[2, 2]);
+    let broke = loop {
+        game.start_hand().unwrap();
+        assert!(game.add_chips(0, 100).is_err());
+        // Both players are all in once the small blind calls.
+        game.act(Call).unwrap();
+        assert_eq!(game.street(), None);
+        if let Some(broke) = (0..2).find(|&p| game.player(p).unwrap().chips == 0) {
+            break broke;
+        }
+    };
+    let winner = 1 - broke;
+    assert!(game.add_chips(broke, 1001).is_err());
+    game.add_chips(broke, 500).unwrap();
+    assert!(game.add_chips(winner, 997).is_err());
+    game.add_chips(winner, 996).unwrap();
+    let transactions: Vec<_> = game
+        .ledger()
+        .entries()
+        .iter()
+        .filter(|entry| matches!(entry.transaction, Rebuy(_) | TopUp(_)))
+        .map(|entry| (entry.player, entry.transaction))
+        .collect();
+    assert_eq!(
+        transactions,
+        [(broke as u64, Rebuy(500)), (winner as u64, TopUp(996))]
+    );
+    assert_eq!(game.ledger().bought_in(), 1500);
+    assert_eq!(total_chips(&game), 1500);
+}
+
+#[test]
+fn ledger() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    game.act(Raise(6)).unwrap();
+    game.act(Fold).unwrap();
+    game.act(Fold).unwrap();
+    let transactions: Vec<_> = game
+        .ledger()
+        .hand(1)
+        .map(|entry| (entry.player, entry.transaction))
+        .collect();
+    assert_eq!(
+        transactions,
+        [
+            (1, Transaction::Blind(1)),
+            (2, Transaction::Blind(2)),
+            (0, Transaction::Bet(6)),
+            (0, Returned(4)),
+            (0, Award(5)),
+        ]
+    );
+    game.remove_player(0).unwrap();
+    assert_eq!(game.ledger().cashed_out(), 203);
+    assert_eq!(game.check_chips(), Ok(()));
+}
+
+#[test]
+fn random_play() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut options = options(BettingStructure::NoLimit);
+    options.ante = 1;
+    options.straddle = Straddle::UnderTheGun;
+    let mut game = game(options, &[100, 200, 300, 400, 500, 600]);
+    game.set_seed(7);
+    for _ in 0..200 {
+        if game.start_hand().is_err() {
+            break;
+        }
+        while game.street().is_some() {
+            let action = match rng.gen_range(0..6) {
+                0 => Fold,
+                1 => Check,
+                2 | 3 => Call,
+                4 => Raise(game.raise_range().map_or(0, |(min, _)| min)),
+                _ => AllIn,
+            };
+            if game.act(action).is_err() {
+                continue;
+            }
+            // Every action is also checked in debug builds.
+            assert_eq!(game.check_chips(), Ok(()));
+        }
+        if rng.gen_bool(0.1) {
+            let position = rng.gen_range(0..6);
+            let _ = game.sit_out(position);
+            let _ = game.sit_in((position + 1) % 6);
+        }
+    }
+    assert_eq!(total_chips(&game), 2100);
+}
+
+#[test]
+fn rake() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.rake = Rake {
+        basis_points: 500,
+        caps: vec![(2, 1), (3, 3)],
+        no_flop_no_drop: true,
+    };
+    let mut game = game(options, &[200, 200, 200]);
+    game.start_hand().unwrap();
+    // No flop, no drop.
+    game.act(Raise(10)).unwrap();
+    fold_around(&mut game);
+    assert_eq!(game.ledger().rake_for(1), 0);
+    game.start_hand().unwrap();
+    game.act(Raise(40)).unwrap();
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    game.act(Bet(100)).unwrap();
+    fold_around(&mut game);
+    // 5% of 120 is 6, capped at 3 for three players.
+    assert_eq!(game.ledger().rake_for(2), 3);
+    assert_eq!(total_chips(&game), 597);
+    game.remove_player(0).unwrap();
+    game.start_hand().unwrap();
+    game.act(Raise(10)).unwrap();
+    game.act(Call).unwrap();
+    game.act(Bet(10)).unwrap();
+    game.act(Fold).unwrap();
+    // Capped at 1 heads up.
+    assert_eq!(game.ledger().rake_for(3), 1);
+    assert_eq!(game.ledger().rake(), 4);
+}
+
+#[test]
+fn rake_rounding() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.rake.basis_points = 460;
+    let mut game = game(options, &[500, 500, 500]);
+    game.start_hand().unwrap();
+    game.act(AllIn).unwrap();
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    // 4.6% of 1500 is 69, which floating point rounds down to 68.
+    assert_eq!(game.ledger().rake_for(1), 69);
+    assert_eq!(total_chips(&game), 1431);
+}
+
+#[test]
+fn hand_history() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 100, 300]);
+    game.sit_out(2).unwrap();
+    game.start_hand().unwrap();
+    assert!(game.histories().is_empty());
+    // Heads up, the button posts the small blind and acts first.
+    game.act(Raise(6)).unwrap();
+    game.act(AllIn).unwrap();
+    game.act(Call).unwrap();
+    let history = &game.histories()[0];
+    assert_eq!(history.number, 1);
+    assert_eq!(history.button, 0);
+    let stacks: Vec<_> = history
+        .seats
+        .iter()
+        .map(|seat| (seat.position, seat.chips, seat.dealt_in))
+        .collect();
+    assert_eq!(stacks, [(0, 200, true), (1, 100, true), (2, 300, false)]);
+    assert_eq!(
+        history.posts,
+        [
+            Post {
+                position: 0,
+                kind: PostKind::SmallBlind,
+                chips: 1
+            },
+            Post {
+                position: 1,
+                kind: PostKind::BigBlind,
+                chips: 2
+            },
+        ]
+    );
+    assert_eq!(history.hole_cards.len(), 2);
+    let actions: Vec<_> = history
+        .actions_on(Street::Preflop)
+        .map(|a| (a.position, a.action, a.chips, a.all_in))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            (0, Raise(6), 5, false),
+            (1, Raise(100), 98, true),
+            (0, Call, 94, false)
+        ]
+    );
+    assert_eq!(history.board.len(), 5);
+    assert_eq!(history.uncalled, None);
+    assert_eq!(history.showdown.len(), 2);
+    assert_eq!(history.won(0) + history.won(1), 200);
+}
+
+#[test]
+fn events() {
+    let mut game = game(options(BettingStructure::NoLimit), &[100, 10]);
+    // Events are only collected once enabled.
+    assert!(game.take_events().is_empty());
+    game.set_events_enabled(true);
+    game.stack_deck(parse_cards("AhAd 7c2d Ks9h4c 3s 8d").unwrap())
+        .unwrap();
+    game.start_hand().unwrap();
+    game.act(AllIn).unwrap();
+    game.act(Call).unwrap();
+    let events = game.take_events();
+    assert!(game.take_events().is_empty());
+    assert_eq!(
+        events[..3],
+        [
+            GameEvent::HandStarted {
+                number: 1,
+                button: 0
+            },
+            GameEvent::CardsDealt {
+                position: 0,
+                cards: Some(parse_cards("AhAd").unwrap())
+            },
+            GameEvent::CardsDealt {
+                position: 1,
+                cards: Some(parse_cards("7c2d").unwrap())
+            },
+        ]
+    );
+    let visible: Vec<GameEvent> = events[1..3]
+        .iter()
+        .map(|event| event.clone().visible_to(Some(1)))
+        .collect();
+    assert_eq!(
+        visible,
+        [
+            GameEvent::CardsDealt {
+                position: 0,
+                cards: None
+            },
+            GameEvent::CardsDealt {
+                position: 1,
+                cards: Some(parse_cards("7c2d").unwrap())
+            },
+        ]
+    );
+    assert_eq!(
+        events[1].clone().visible_to(None),
+        GameEvent::CardsDealt {
+            position: 0,
+            cards: None
+        }
+    );
+    assert!(events.contains(&GameEvent::BetReturned {
+        position: 0,
+        chips: 90
+    }));
+    assert!(events.contains(&GameEvent::StreetAdvanced {
+        street: Street::River,
+        cards: parse_cards("8d").unwrap()
+    }));
+    let showdowns = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::Showdown { .. }))
+        .count();
+    assert_eq!(showdowns, 2);
+    assert_eq!(
+        events[events.len() - 3..],
+        [
+            GameEvent::PotAwarded(history::Award {
+                position: 0,
+                pot: 0,
+                chips: 20
+            }),
+            GameEvent::Busted { position: 1, id: 1 },
+            GameEvent::HandEnded { number: 1 },
+        ]
+    );
+}
+
+#[test]
+fn views() {
+    let mut game = game(options(BettingStructure::NoLimit), &[100, 100, 100]);
+    game.stack_deck(parse_cards("AhAd 7c2d KcKd Ks9h4c 3s 8d").unwrap())
+        .unwrap();
+    game.start_hand().unwrap();
+    let cards = |view: &GameView| -> Vec<_> {
+        view.seats
+            .iter()
+            .flatten()
+            .map(|player| player.cards.clone())
+            .collect()
+    };
+    let view = game.view_for(1);
+    assert_eq!(view.turn, Some(0));
+    assert_eq!(cards(&view), [None, parse_cards("7c2d").ok(), None]);
+    assert_eq!(cards(&game.spectator_view()), [None, None, None]);
+
+    // Seat 1 folds, so their cards are not shown at showdown.
+    for action in [Call, Fold, Check, Check, Check, Check, Check, Check, Check] {
+        game.act(action).unwrap();
+    }
+    let view = game.spectator_view();
+    assert_eq!(view.turn, None);
+    assert_eq!(
+        cards(&view),
+        [
+            parse_cards("AhAd").ok(),
+            Some(Vec::new()),
+            parse_cards("KcKd").ok()
+        ]
+    );
+}
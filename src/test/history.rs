@@ -0,0 +1,370 @@
+use super::game::{game, options};
+use crate::card::{parse_cards, Rank::*};
+use crate::game::{
+    betting::Action::*,
+    betting::BettingStructure,
+    history::{Post, PostKind},
+    pokerstars,
+    replay::Replay,
+    Game, Street,
+};
+use crate::hand::Hand::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[test]
+fn pokerstars_export() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    game.set_clock(|| 1_700_000_000);
+    game.start_hand().unwrap();
+    game.act(Raise(6)).unwrap();
+    game.act(Fold).unwrap();
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    game.act(Bet(10)).unwrap();
+    game.act(Fold).unwrap();
+    let history = &game.histories()[0];
+    let text = pokerstars::write_hand(history, "Test", None);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[..6],
+        [
+            "PokerStars Hand #1:  Hold'em No Limit (1/2) - 2023/11/14 22:13:20 UTC [2023/11/14 17:13:20 ET]",
+            "Table 'Test' 6-max Seat #1 is the button",
+            "Seat 1: Player 0 (200 in chips)",
+            "Seat 2: Player 1 (200 in chips)",
+            "Seat 3: Player 2 (200 in chips)",
+            "Player 1: posts small blind 1",
+        ]
+    );
+    assert_eq!(lines[7], "*** HOLE CARDS ***");
+    assert!(lines[8].starts_with("Dealt to Player 0 ["));
+    assert_eq!(
+        lines[11..14],
+        [
+            "Player 0: raises 4 to 6",
+            "Player 1: folds",
+            "Player 2: calls 4"
+        ]
+    );
+    assert!(lines[14].starts_with("*** FLOP *** ["));
+    assert_eq!(
+        lines[15..],
+        [
+            "Player 2: checks",
+            "Player 0: bets 10",
+            "Player 2: folds",
+            "Uncalled bet (10) returned to Player 0",
+            "Player 0 collected 13 from pot",
+            "Player 0: doesn't show hand",
+            "*** SUMMARY ***",
+            "Total pot 13 | Rake 0",
+            lines[23],
+            "Seat 1: Player 0 (button) collected (13)",
+            "Seat 2: Player 1 (small blind) folded before Flop",
+            "Seat 3: Player 2 (big blind) folded on the Flop",
+        ]
+    );
+    assert!(lines[23].starts_with("Board ["));
+
+    // Eastern time is four hours behind in the summer.
+    let mut summer = history.clone();
+    summer.time = 1_625_155_200;
+    let text = pokerstars::write_hand(&summer, "Test", None);
+    assert!(text
+        .lines()
+        .next()
+        .unwrap()
+        .ends_with("2021/07/01 16:00:00 UTC [2021/07/01 12:00:00 ET]"));
+    assert_eq!(pokerstars::read_hands(&text).unwrap()[0].time, summer.time);
+
+    // Only the hero's cards are dealt face up.
+    let text = pokerstars::write_hand(history, "Test", Some(2));
+    let dealt: Vec<&str> = text
+        .lines()
+        .filter(|line| line.starts_with("Dealt to"))
+        .collect();
+    assert_eq!(dealt.len(), 1);
+    assert!(dealt[0].starts_with("Dealt to Player 2 ["));
+}
+
+/// Plays hands with random actions.
+fn random_game(hands: usize) -> Game {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut options = options(BettingStructure::NoLimit);
+    options.ante = 1;
+    let mut game = game(options, &[100, 200, 300, 400, 500, 600]);
+    game.set_seed(7);
+    for _ in 0..hands {
+        if game.start_hand().is_err() {
+            break;
+        }
+        while game.street().is_some() {
+            let action = match rng.gen_range(0..6) {
+                0 => Fold,
+                1 => Check,
+                2 | 3 => Call,
+                4 => Raise(game.raise_range().map_or(0, |(min, _)| min)),
+                _ => AllIn,
+            };
+            let _ = game.act(action);
+        }
+    }
+    game
+}
+
+#[test]
+fn pokerstars_round_trip() {
+    let game = random_game(50);
+    let text = pokerstars::write_hands(game.histories(), "Test", None);
+    let read = pokerstars::read_hands(&text).unwrap();
+    assert_eq!(read.len(), game.histories().len());
+    for (read, written) in read.iter().zip(game.histories()) {
+        assert_eq!(read.number, written.number);
+        assert_eq!(read.time, written.time);
+        assert_eq!(read.button, written.button);
+        assert_eq!(read.seats, written.seats);
+        assert_eq!(read.posts, written.posts);
+        assert_eq!(read.hole_cards, written.hole_cards);
+        assert_eq!(read.actions, written.actions);
+        assert_eq!(read.board, written.board);
+        assert_eq!(read.uncalled, written.uncalled);
+        assert_eq!(read.showdown, written.showdown);
+        assert_eq!(read.awards, written.awards);
+        assert_eq!(read.rake, written.rake);
+    }
+}
+
+const CASH_HAND: &str = "\
+PokerStars Hand #222: Hold'em No Limit ($0.25/$0.50 USD) - 2021/03/04 5:06:07 CET [2021/03/03 23:06:07 ET]
+Table 'Alpha III' 6-max Seat #2 is the button
+Seat 1: Ann Lee ($50 in chips)
+Seat 2: bob ($61.25 in chips)
+Seat 4: Cy ($20 in chips) is sitting out
+Seat 5: Dee ($48.50 in chips)
+Dee: posts small blind $0.25
+Ann Lee: posts big blind $0.50
+*** HOLE CARDS ***
+Dealt to bob [Qh Kd]
+bob: raises $1 to $1.50
+Dee: folds
+Ann Lee said, \"nh\"
+Ann Lee: calls $1
+*** FLOP *** [2h 7d 9s]
+Ann Lee: checks
+bob: bets $59.75 and is all-in
+Ann Lee: calls $48.50 and is all-in
+Uncalled bet ($11.25) returned to bob
+*** TURN *** [2h 7d 9s] [Tc]
+*** RIVER *** [2h 7d 9s Tc] [Js]
+*** SHOW DOWN ***
+Ann Lee: shows [8s 8c] (a straight, Seven to Jack)
+bob: shows [Qh Kd] (a straight, Nine to King)
+bob collected $99.75 from pot
+*** SUMMARY ***
+Total pot $100.25 | Rake $0.50
+Board [2h 7d 9s Tc Js]
+Seat 1: Ann Lee (big blind) showed [8s 8c] and lost with a straight, Seven to Jack
+Seat 2: bob (button) showed [Qh Kd] and won ($99.75) with a straight, Nine to King
+Seat 5: Dee (small blind) folded before Flop
+";
+
+#[test]
+fn pokerstars_import() {
+    let text = format!("Some header\n\n{CASH_HAND}\n\n\n{CASH_HAND}");
+    let hands = pokerstars::read_hands(&text).unwrap();
+    assert_eq!(hands.len(), 2);
+    let hand = &hands[0];
+    assert_eq!(hand.number, 222);
+    // 23:06:07 Eastern Standard Time.
+    assert_eq!(hand.time, 1614830767);
+    assert_eq!(hand.options.big_blind, 50);
+    assert_eq!(hand.button, 1);
+    let seats: Vec<_> = hand
+        .seats
+        .iter()
+        .map(|seat| (seat.position, seat.name.as_str(), seat.chips, seat.dealt_in))
+        .collect();
+    assert_eq!(
+        seats,
+        [
+            (0, "Ann Lee", 5000, true),
+            (1, "bob", 6125, true),
+            (3, "Cy", 2000, false),
+            (4, "Dee", 4850, true)
+        ]
+    );
+    let actions: Vec<_> = hand
+        .actions
+        .iter()
+        .map(|a| (a.position, a.action, a.chips))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            (1, Raise(150), 150),
+            (4, Fold, 0),
+            (0, Call, 100),
+            (0, Check, 0),
+            (1, Bet(5975), 5975),
+            (0, Call, 4850),
+        ]
+    );
+    assert_eq!(hand.uncalled, Some((1, 1125)));
+    assert_eq!(hand.board, parse_cards("2h7d9sTcJs").unwrap());
+    assert_eq!(hand.hole_cards[0], (1, parse_cards("QhKd").unwrap()));
+    assert_eq!(hand.showdown[1], (1, Straight(King)));
+    assert_eq!(hand.won(1), 9975);
+    assert_eq!(hand.rake, 50);
+}
+
+#[test]
+fn replay_imported() {
+    let hand = pokerstars::read_hands(CASH_HAND).unwrap().remove(0);
+    let mut replay = Replay::new(hand).unwrap();
+    assert_eq!(replay.verify(), Ok(()));
+    assert_eq!(replay.game().ledger().rake(), 50);
+}
+
+#[test]
+fn pokerstars_import_errors() {
+    let text = CASH_HAND.replace("[2h 7d 9s]", "[2h 7d 9x]");
+    let error = pokerstars::read_hands(&format!("\n{text}")).unwrap_err();
+    assert_eq!(error, "Line 16: Invalid card '9x'");
+    let text = CASH_HAND.replace("Hold'em", "Omaha");
+    assert!(pokerstars::read_hands(&text).is_err());
+    let text = CASH_HAND.replace("bets $59.75", "bets $59");
+    let error = pokerstars::read_hands(&text).unwrap_err();
+    assert_eq!(error, "Line 17: All in for 5900 with 5975 behind");
+    let text = CASH_HAND.replace(" [2021/03/03 23:06:07 ET]", "");
+    let error = pokerstars::read_hands(&text).unwrap_err();
+    assert_eq!(error, "Line 1: Unsupported time zone 'CET'");
+}
+
+#[test]
+fn pokerstars_import_tournament() {
+    // The buy-in is in dollars, but the stakes and stacks are in chips.
+    let text = "\
+PokerStars Hand #5: Tournament #9, $10+$1 USD Hold'em No Limit - Level I (10/20) - 2021/07/01 12:00:00 ET
+Table '9 1' 9-max Seat #1 is the button
+Seat 1: Ann (1500 in chips)
+Seat 2: bob (1500 in chips)
+Ann: posts small blind 10
+bob: posts big blind 20
+*** HOLE CARDS ***
+Ann: folds
+Uncalled bet (10) returned to bob
+bob collected 20 from pot
+*** SUMMARY ***
+Total pot 20 | Rake 0
+";
+    let hands = pokerstars::read_hands(text).unwrap();
+    let hand = &hands[0];
+    assert_eq!(hand.options.big_blind, 20);
+    assert_eq!(hand.seats[0].chips, 1500);
+    // 12:00:00 Eastern Daylight Time.
+    assert_eq!(hand.time, 1625155200);
+}
+
+#[test]
+fn replay() {
+    let game = random_game(50);
+    for history in game.histories() {
+        let mut replay = Replay::new(history.clone()).unwrap();
+        assert_eq!(replay.verify(), Ok(()));
+    }
+    // Hands read back from text have no seed, so they are dealt from a stacked deck.
+    let text = pokerstars::write_hands(game.histories(), "Test", None);
+    for history in pokerstars::read_hands(&text).unwrap() {
+        assert_eq!(history.seed, None);
+        assert_eq!(Replay::new(history).unwrap().verify(), Ok(()));
+    }
+}
+
+#[test]
+fn replay_big_blind_all_in_on_ante() {
+    let mut options = options(BettingStructure::NoLimit);
+    options.ante = 1;
+    let mut game = game(options, &[100, 100, 1]);
+    game.start_hand().unwrap();
+    // The ante puts the big blind all in, so the bet to call is the small blind.
+    game.act(Call).unwrap();
+    while game.street().is_some() {
+        game.act(Check).unwrap();
+    }
+    let history = &game.histories()[0];
+    assert!(history.posts.contains(&Post {
+        position: 2,
+        kind: PostKind::BigBlind,
+        chips: 0
+    }));
+    let mut replay = Replay::new(history.clone()).unwrap();
+    assert_eq!(replay.verify(), Ok(()));
+    let text = pokerstars::write_hands(game.histories(), "Test", None);
+    let imported = pokerstars::read_hands(&text).unwrap().remove(0);
+    assert_eq!(Replay::new(imported).unwrap().verify(), Ok(()));
+}
+
+#[test]
+fn spread_limit_round_trip() {
+    let betting = BettingStructure::SpreadLimit {
+        min_bet: 2,
+        max_bet: 10,
+    };
+    let mut game = game(options(betting), &[200, 200, 200]);
+    game.start_hand().unwrap();
+    game.act(Raise(12)).unwrap();
+    game.act(Call).unwrap();
+    game.act(Call).unwrap();
+    game.act(Check).unwrap();
+    game.act(Bet(10)).unwrap();
+    game.act(Raise(20)).unwrap();
+    game.act(Fold).unwrap();
+    game.act(Call).unwrap();
+    while game.street().is_some() {
+        game.act(Check).unwrap();
+    }
+    let text = pokerstars::write_hands(game.histories(), "Test", None);
+    assert!(text.starts_with("PokerStars Hand #1:  Hold'em Spread Limit (1/2 spread 2-10)"));
+    let history = pokerstars::read_hands(&text).unwrap().remove(0);
+    assert_eq!(history.options.betting, betting);
+    assert_eq!(Replay::new(history).unwrap().verify(), Ok(()));
+}
+
+#[test]
+fn replay_step_through() {
+    let mut game = game(options(BettingStructure::NoLimit), &[200, 200, 200]);
+    let deck = parse_cards("AhAd KhKd 2c7s QsJs Th 9h 8h").unwrap();
+    game.stack_deck(deck).unwrap();
+    game.start_hand().unwrap();
+    assert_eq!(game.player(0).unwrap().cards, parse_cards("AhAd").unwrap());
+    game.act(Raise(6)).unwrap();
+    game.act(Call).unwrap();
+    game.act(Fold).unwrap();
+    game.act(Check).unwrap();
+    game.act(Bet(10)).unwrap();
+    game.act(Call).unwrap();
+    assert_eq!(game.board(), parse_cards("QsJsTh9h").unwrap());
+    game.act(Check).unwrap();
+    game.act(AllIn).unwrap();
+    game.act(Call).unwrap();
+    let history = game.histories()[0].clone();
+    assert_eq!(history.showdown[1], (1, Straight(King)));
+
+    let mut replay = Replay::new(history).unwrap();
+    assert_eq!((replay.index(), replay.len()), (0, 9));
+    replay.seek(5).unwrap();
+    assert_eq!(replay.game().street(), Some(Street::Flop));
+    assert_eq!(replay.game().turn(), 1);
+    assert_eq!(replay.game().to_call(), 10);
+    assert_eq!(replay.game().board(), parse_cards("QsJsTh").unwrap());
+    assert!(replay.step_forward().unwrap());
+    assert_eq!(replay.game().street(), Some(Street::Turn));
+    assert!(replay.step_back().unwrap());
+    assert!(replay.step_back().unwrap());
+    assert_eq!(replay.index(), 4);
+    assert_eq!(replay.game().to_call(), 0);
+    assert!(replay.seek(10).is_err());
+    assert_eq!(replay.verify(), Ok(()));
+    assert!(!replay.step_forward().unwrap());
+    assert_eq!(replay.game().player(1).unwrap().chips, 402);
+}